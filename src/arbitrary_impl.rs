@@ -0,0 +1,74 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{
+    validate::Fields, Code, Fan, Message, Mode, Model, RawFrame, SwingMode, Temperature,
+    TemperatureDisplay, TimerSetting, UnitId,
+};
+
+/// Only produces states [`Message::validate`] accepts for [`Model::DEFAULT`], any fields
+/// made valid with [`Message::sanitize`].
+impl<'a> Arbitrary<'a> for Message {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let model = Model::DEFAULT;
+        let fields = Fields {
+            mode: *u.choose(&[Mode::Auto, Mode::Cold, Mode::Dry, Mode::Wind, Mode::Hot])?,
+            on: u.arbitrary()?,
+            fan: *u.choose(&[Fan::Auto, Fan::Level1, Fan::Level2, Fan::Level3])?,
+            swing: u.arbitrary()?,
+            temperature: if u.arbitrary()? {
+                Temperature::Fahrenheit(
+                    u.int_in_range(model.min_fahrenheit..=model.max_fahrenheit)?,
+                )
+            } else {
+                Temperature::Centigrade(u.int_in_range(model.min_celsius..=model.max_celsius)?)
+            },
+            timer: u.arbitrary()?,
+            turbo: u.arbitrary()?,
+            sleep: u.arbitrary()?,
+            light: u.arbitrary()?,
+            health: u.arbitrary()?,
+            dry: u.arbitrary()?,
+            ventilate: u.arbitrary()?,
+            v_swing: SwingMode::decode(u.arbitrary()?),
+            h_swing: SwingMode::decode(u.arbitrary()?),
+            temperature_display: TemperatureDisplay::decode(u.arbitrary()?),
+            i_feel: u.arbitrary()?,
+            wifi: u.arbitrary()?,
+            unit_id: if u.arbitrary()? {
+                Some(UnitId(u.int_in_range(0..=UnitId::MAX)?))
+            } else {
+                None
+            },
+            se: u.arbitrary()?,
+            econo: u.arbitrary()?,
+        };
+        Ok(Message::from_fields(model, fields))
+    }
+}
+
+impl<'a> Arbitrary<'a> for TimerSetting {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let half_hours = u.int_in_range(0..=TimerSetting::MAX_HALF_HOURS)?;
+        Ok(TimerSetting::rounded(u.arbitrary()?, half_hours))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Code {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[
+            Code::Start,
+            Code::Continue,
+            Code::End,
+            Code::Short,
+            Code::Long,
+        ])?)
+    }
+}
+
+/// Any 70 codes, mostly not a valid frame. Use [`RawFrame::repair`] or
+/// [`RawFrame::from_message`] for valid ones.
+impl<'a> Arbitrary<'a> for RawFrame {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(RawFrame(u.arbitrary()?))
+    }
+}
@@ -0,0 +1,208 @@
+use crate::Message;
+#[cfg(feature = "decode")]
+use crate::{DecodeError, Model};
+
+/// Number of data bits in a frame: two 32-bit blocks with the 3-bit magic in between.
+pub const PAYLOAD_BITS: usize = 67;
+
+/// Magic bits packed LSB first, as stored in the last byte of [`Message::to_bytes`].
+#[cfg(any(feature = "encode", feature = "decode"))]
+const MAGIC_BYTE: u8 = 0b010;
+
+impl Message {
+    /// Packs the frame into 9 bytes, markers excluded.
+    ///
+    /// The first 8 bytes are the state in transmission order with each byte LSB first,
+    /// the same way other tools (e.g. IRremoteESP8266) print Gree state. The last byte
+    /// holds the 3 magic bits between the blocks, also LSB first.
+    #[cfg(feature = "encode")]
+    pub fn to_bytes(&self) -> [u8; 9] {
+        let mut bytes = [0; 9];
+        bytes[..8].copy_from_slice(&self.remote_state);
+        bytes[8] = MAGIC_BYTE;
+        bytes
+    }
+
+    /// Unpacks bytes produced by [`Message::to_bytes`], checking the magic and checksum.
+    #[cfg(feature = "decode")]
+    pub fn from_bytes(bytes: &[u8; 9]) -> Result<Self, DecodeError> {
+        if bytes[8] != MAGIC_BYTE {
+            return Err(DecodeError::InvalidMagic);
+        }
+        let mut message = Self {
+            remote_state: [0; 8],
+            model: Model::DEFAULT,
+        };
+        message.remote_state.copy_from_slice(&bytes[..8]);
+        message.check_checksum()?;
+        Ok(message)
+    }
+
+    /// The data bits of the frame, without the start, continue and end markers.
+    #[cfg(feature = "encode")]
+    pub fn encode_payload_bits(&self) -> [bool; PAYLOAD_BITS] {
+        let words = self.to_payload_words();
+        let mut bits = [false; PAYLOAD_BITS];
+        for (i, v) in bits.iter_mut().enumerate() {
+            *v = words[i / 64] >> (i % 64) & 1 != 0;
+        }
+        bits
+    }
+
+    /// The [payload bits](Message::encode_payload_bits) packed LSB first into two words,
+    /// the last 3 bits in the second one. Assembled with a few shifts, which is faster
+    /// than going bit by bit when processing captures in bulk.
+    #[cfg(feature = "encode")]
+    pub fn to_payload_words(&self) -> [u64; 2] {
+        let state = u64::from_le_bytes(self.remote_state);
+        let block1 = state & 0xFFFF_FFFF;
+        let block2 = state >> 32;
+        [
+            block1 | (MAGIC_BYTE as u64) << 32 | block2 << 35,
+            block2 >> 29,
+        ]
+    }
+
+    /// Decodes [payload words](Message::to_payload_words), checking the magic and
+    /// checksum.
+    ///
+    /// Bits after the first [`PAYLOAD_BITS`] are ignored.
+    #[cfg(feature = "decode")]
+    pub fn from_payload_words(words: [u64; 2]) -> Result<Self, DecodeError> {
+        if (words[0] >> 32 & 0b111) as u8 != MAGIC_BYTE {
+            return Err(DecodeError::InvalidMagic);
+        }
+        let block1 = words[0] & 0xFFFF_FFFF;
+        let block2 = words[0] >> 35 | (words[1] & 0b111) << 29;
+        let message = Self {
+            remote_state: (block1 | block2 << 32).to_le_bytes(),
+            model: Model::DEFAULT,
+        };
+        message.check_checksum()?;
+        Ok(message)
+    }
+
+    /// The [payload bits](Message::encode_payload_bits) packed LSB first into 9 bytes, in
+    /// the order they are sent. Unlike [`Message::to_bytes`], the magic stays between the
+    /// blocks and the last 5 bits are 0.
+    #[cfg(feature = "encode")]
+    pub fn to_packed_bits(&self) -> [u8; 9] {
+        let [low, high] = self.to_payload_words();
+        let mut bytes = [0; 9];
+        bytes[..8].copy_from_slice(&low.to_le_bytes());
+        bytes[8] = high as u8;
+        bytes
+    }
+
+    /// Decodes [packed bits](Message::to_packed_bits), for receivers that store each data
+    /// bit as it is classified instead of keeping [`Code`](crate::Code)s.
+    ///
+    /// The last 5 bits are ignored.
+    #[cfg(feature = "decode")]
+    pub fn from_packed_bits(bytes: &[u8; 9]) -> Result<Self, DecodeError> {
+        let mut low = [0; 8];
+        low.copy_from_slice(&bytes[..8]);
+        Self::from_payload_words([u64::from_le_bytes(low), bytes[8] as u64])
+    }
+
+    /// Decodes the data bits of a frame whose markers were already stripped.
+    ///
+    /// Bits after the first [`PAYLOAD_BITS`] are ignored.
+    #[cfg(feature = "decode")]
+    pub fn decode_payload_bits(bits: &[bool]) -> Result<Self, DecodeError> {
+        let bits = bits.get(..PAYLOAD_BITS).ok_or(DecodeError::Eof)?;
+        let mut words = [0; 2];
+        for (i, &bit) in bits.iter().enumerate() {
+            words[i / 64] |= (bit as u64) << (i % 64);
+        }
+        Self::from_payload_words(words)
+    }
+}
+
+/// Packs both blocks into one value, the first block in the low 32 bits.
+///
+/// Each block is little endian, so bit `n` is the `n`-th data bit sent.
+#[cfg(feature = "encode")]
+impl From<&Message> for u64 {
+    fn from(message: &Message) -> u64 {
+        u64::from_le_bytes(message.remote_state)
+    }
+}
+
+/// Unpacks a value produced by `u64::from`, checking the checksum.
+#[cfg(feature = "decode")]
+impl TryFrom<u64> for Message {
+    type Error = DecodeError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        let message = Self {
+            remote_state: value.to_le_bytes(),
+            model: Model::DEFAULT,
+        };
+        message.check_checksum()?;
+        Ok(message)
+    }
+}
+
+/// The first and second block, each little endian.
+#[cfg(feature = "encode")]
+impl From<&Message> for (u32, u32) {
+    fn from(message: &Message) -> (u32, u32) {
+        let value = u64::from(message);
+        (value as u32, (value >> 32) as u32)
+    }
+}
+
+#[cfg(feature = "decode")]
+impl TryFrom<(u32, u32)> for Message {
+    type Error = DecodeError;
+
+    fn try_from((block1, block2): (u32, u32)) -> Result<Self, Self::Error> {
+        Message::try_from(block1 as u64 | (block2 as u64) << 32)
+    }
+}
+
+#[cfg(feature = "bitvec")]
+impl Message {
+    /// The [payload bits](Message::encode_payload_bits), padded with zeros to 72 bits.
+    #[cfg(feature = "encode")]
+    pub fn to_bitarray(&self) -> bitvec::array::BitArray<[u8; 9], bitvec::order::Lsb0> {
+        bitvec::array::BitArray::new(self.to_packed_bits())
+    }
+
+    /// Decodes [payload bits](Message::decode_payload_bits) from any bit slice.
+    ///
+    /// Bits after the first [`PAYLOAD_BITS`] are ignored.
+    #[cfg(feature = "decode")]
+    pub fn from_bitslice<T, O>(bits: &bitvec::slice::BitSlice<T, O>) -> Result<Self, DecodeError>
+    where
+        T: bitvec::store::BitStore,
+        O: bitvec::order::BitOrder,
+    {
+        let mut payload = [false; PAYLOAD_BITS];
+        if bits.len() < PAYLOAD_BITS {
+            return Err(DecodeError::Eof);
+        }
+        for (v, bit) in payload.iter_mut().zip(bits.iter().by_vals()) {
+            *v = bit;
+        }
+        Self::decode_payload_bits(&payload)
+    }
+}
+
+#[cfg(all(test, feature = "encode", feature = "decode", feature = "fmt"))]
+mod tests {
+    use crate::{presets, Message};
+
+    #[test]
+    fn packed_bits_decode_in_arrival_order() {
+        let msg = presets::eco_night();
+        let mut bytes = [0; 9];
+        for (i, bit) in msg.encode_payload_bits().into_iter().enumerate() {
+            bytes[i / 8] |= (bit as u8) << (i % 8);
+        }
+        assert_eq!(bytes, msg.to_packed_bits());
+        assert_eq!(Message::from_packed_bits(&bytes), Ok(msg));
+        assert_eq!(Message::from_bytes(&msg.to_bytes()), Ok(msg));
+    }
+}
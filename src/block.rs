@@ -0,0 +1,110 @@
+#[cfg(any(feature = "encode", feature = "decode"))]
+use crate::Code;
+#[cfg(feature = "decode")]
+use crate::{check_magic_code3, check_marker, decode_block};
+use crate::{DecodeError, Fan, Message, Mode, Model, Temperature, TimerSetting};
+
+/// Bytes 0–3 of the state, sent between the start marker and the magic.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct Block1(pub [u8; 4]);
+
+/// Bytes 4–7 of the state, sent between the continue and end markers. The checksum is
+/// in the upper nibble of the last byte.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct Block2(pub [u8; 4]);
+
+macro_rules! impl_block {
+    ($block:ident) => {
+        impl $block {
+            /// Number of codes in the block.
+            pub const LEN: usize = 32;
+
+            /// The data codes of the block, LSB first, without any marker or magic.
+            #[cfg(feature = "encode")]
+            pub fn encode(&self) -> impl Iterator<Item = Code> + Clone {
+                let bytes = self.0;
+                (0..Self::LEN).map(move |i| Code::from(bytes[i / 8] >> (i % 8) & 1 != 0))
+            }
+
+            /// Reads the next [`LEN`](Self::LEN) codes from `codes`, which must all be data
+            /// codes.
+            #[cfg(feature = "decode")]
+            pub fn decode(codes: &mut impl Iterator<Item = Code>) -> Result<Self, DecodeError> {
+                let mut bytes = [0; 4];
+                decode_block(codes, &mut bytes)?;
+                Ok(Self(bytes))
+            }
+        }
+    };
+}
+
+impl_block!(Block1);
+impl_block!(Block2);
+
+/// The settings carried by the first block, for captures that lack the second one.
+impl Block1 {
+    pub fn mode(&self) -> Result<Mode, DecodeError> {
+        Mode::decode(self.0[0])
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.0[0] >> 3 & 1 != 0
+    }
+
+    pub fn fan(&self) -> Fan {
+        Fan::decode(self.0[0] >> 4)
+    }
+
+    pub fn temperature(&self, model: &Model) -> Result<Temperature, DecodeError> {
+        let temp = Temperature::decode(self.0[1], self.0[3] >> 2 & 1 != 0, self.0[3] >> 3 & 1 != 0);
+        if model.supports_temperature(temp) {
+            Ok(temp)
+        } else {
+            Err(DecodeError::InvalidTemperature)
+        }
+    }
+
+    pub fn timer(&self) -> Result<TimerSetting, DecodeError> {
+        TimerSetting::decode(self.0[1] >> 4 | self.0[2] << 4)
+    }
+}
+
+impl Message {
+    /// Decodes the first 36 codes of a frame: the start marker, the first block and the
+    /// magic.
+    ///
+    /// Receivers often time out on the gap after the magic and miss the second block,
+    /// which still leaves mode, power, fan, temperature and timer. There is no checksum
+    /// to verify them against.
+    #[cfg(feature = "decode")]
+    pub fn decode_block1(codes: impl IntoIterator<Item = Code>) -> Result<Block1, DecodeError> {
+        let mut iter = codes.into_iter();
+        check_marker(&mut iter, Code::Start)?;
+        let block = Block1::decode(&mut iter)?;
+        check_magic_code3(&mut iter)?;
+        Ok(block)
+    }
+
+    /// Puts a message together from its two blocks, keeping the checksum from `block2`.
+    pub fn from_blocks(block1: Block1, block2: Block2) -> Self {
+        let mut remote_state = [0; 8];
+        remote_state[..4].copy_from_slice(&block1.0);
+        remote_state[4..].copy_from_slice(&block2.0);
+        Self {
+            remote_state,
+            model: Model::DEFAULT,
+        }
+    }
+
+    pub fn block1(&self) -> Block1 {
+        let [a, b, c, d, ..] = self.remote_state;
+        Block1([a, b, c, d])
+    }
+
+    pub fn block2(&self) -> Block2 {
+        let [.., a, b, c, d] = self.remote_state;
+        Block2([a, b, c, d])
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{
+    Fan, Message, Mode, Model, SwingMode, Temperature, TemperatureDisplay, TimerSetting, UnitId,
+};
+
+/// Chainable construction of a [`Message`].
+///
+/// Fields that are never set keep the values of [`Message::new`].
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct MessageBuilder {
+    model: Option<Model>,
+    mode: Option<Mode>,
+    on: Option<bool>,
+    fan: Option<Fan>,
+    swing: Option<bool>,
+    sleep: Option<bool>,
+    temperature: Option<Temperature>,
+    timer: Option<TimerSetting>,
+    turbo: Option<bool>,
+    light: Option<bool>,
+    health: Option<bool>,
+    dry: Option<bool>,
+    ventilate: Option<bool>,
+    v_swing: Option<SwingMode>,
+    h_swing: Option<SwingMode>,
+    temperature_display: Option<TemperatureDisplay>,
+    i_feel: Option<bool>,
+    wifi: Option<bool>,
+    unit_id: Option<Option<UnitId>>,
+    econo: Option<bool>,
+    se: Option<bool>,
+}
+
+impl MessageBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn model(mut self, model: Model) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    pub fn on(mut self, on: bool) -> Self {
+        self.on = Some(on);
+        self
+    }
+
+    pub fn fan(mut self, fan: Fan) -> Self {
+        self.fan = Some(fan);
+        self
+    }
+
+    pub fn swing(mut self, swing: bool) -> Self {
+        self.swing = Some(swing);
+        self
+    }
+
+    pub fn sleep(mut self, sleep: bool) -> Self {
+        self.sleep = Some(sleep);
+        self
+    }
+
+    pub fn temperature(mut self, temp: Temperature) -> Self {
+        self.temperature = Some(temp);
+        self
+    }
+
+    pub fn timer(mut self, setting: TimerSetting) -> Self {
+        self.timer = Some(setting);
+        self
+    }
+
+    pub fn turbo(mut self, turbo: bool) -> Self {
+        self.turbo = Some(turbo);
+        self
+    }
+
+    pub fn light(mut self, light: bool) -> Self {
+        self.light = Some(light);
+        self
+    }
+
+    pub fn health(mut self, health: bool) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    pub fn dry(mut self, dry: bool) -> Self {
+        self.dry = Some(dry);
+        self
+    }
+
+    pub fn ventilate(mut self, ventilate: bool) -> Self {
+        self.ventilate = Some(ventilate);
+        self
+    }
+
+    pub fn v_swing(mut self, mode: SwingMode) -> Self {
+        self.v_swing = Some(mode);
+        self
+    }
+
+    pub fn h_swing(mut self, mode: SwingMode) -> Self {
+        self.h_swing = Some(mode);
+        self
+    }
+
+    pub fn temperature_display(mut self, temp_display: TemperatureDisplay) -> Self {
+        self.temperature_display = Some(temp_display);
+        self
+    }
+
+    pub fn i_feel(mut self, i_feel: bool) -> Self {
+        self.i_feel = Some(i_feel);
+        self
+    }
+
+    pub fn wifi(mut self, wifi: bool) -> Self {
+        self.wifi = Some(wifi);
+        self
+    }
+
+    pub fn unit_id(mut self, unit_id: Option<UnitId>) -> Self {
+        self.unit_id = Some(unit_id);
+        self
+    }
+
+    pub fn econo(mut self, econo: bool) -> Self {
+        self.econo = Some(econo);
+        self
+    }
+
+    pub fn se(mut self, se: bool) -> Self {
+        self.se = Some(se);
+        self
+    }
+
+    pub fn build(&self) -> Message {
+        // The model and mode go first, the temperature and SE setters depend on them.
+        let mut msg = Message::with_model(self.model.unwrap_or_default());
+        if let Some(mode) = self.mode {
+            msg.set_mode(mode);
+        }
+        if let Some(on) = self.on {
+            msg.set_on(on);
+        }
+        if let Some(fan) = self.fan {
+            msg.set_fan(fan);
+        }
+        if let Some(swing) = self.swing {
+            msg.set_swing(swing);
+        }
+        if let Some(sleep) = self.sleep {
+            msg.set_sleep(sleep);
+        }
+        if let Some(temp) = self.temperature {
+            msg.set_temperature(temp);
+        }
+        if let Some(setting) = &self.timer {
+            // Every TimerSetting this crate hands out is representable.
+            let _ = msg.set_timer(setting);
+        }
+        if let Some(turbo) = self.turbo {
+            msg.set_turbo(turbo);
+        }
+        if let Some(light) = self.light {
+            msg.set_light(light);
+        }
+        if let Some(health) = self.health {
+            msg.set_health(health);
+        }
+        if let Some(dry) = self.dry {
+            msg.set_dry(dry);
+        }
+        if let Some(ventilate) = self.ventilate {
+            msg.set_ventilateo(ventilate);
+        }
+        if let Some(mode) = self.v_swing {
+            msg.set_v_swing(mode);
+        }
+        if let Some(mode) = self.h_swing {
+            msg.set_h_swing(mode);
+        }
+        if let Some(temp_display) = self.temperature_display {
+            msg.set_temperature_display(temp_display);
+        }
+        if let Some(i_feel) = self.i_feel {
+            msg.set_i_feel(i_feel);
+        }
+        if let Some(wifi) = self.wifi {
+            msg.set_wifi(wifi);
+        }
+        if let Some(unit_id) = self.unit_id {
+            msg.set_unit_id(unit_id);
+        }
+        if let Some(econo) = self.econo {
+            msg.set_econo(econo);
+        }
+        if let Some(se) = self.se {
+            msg.set_se(se);
+        }
+        msg
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{Message, PULSE_LEN};
+
+/// The [pulses](Message::encode_pulses) of the last `N` messages sent, so sending the
+/// same state again skips encoding.
+///
+/// Messages are compared with `==`, which includes the model. When full, the least
+/// recently used entry is replaced. `N` must be at least 1.
+#[derive(Clone)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct FrameCache<const N: usize> {
+    entries: [Option<Entry>; N],
+    tick: u32,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+struct Entry {
+    message: Message,
+    pulses: [u16; PULSE_LEN],
+    used: u32,
+}
+
+impl<const N: usize> FrameCache<N> {
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "a FrameCache needs room for at least one frame") };
+        Self {
+            entries: [const { None }; N],
+            tick: 0,
+        }
+    }
+
+    /// The pulses of `message`, encoded only if they are not cached yet.
+    pub fn get(&mut self, message: &Message) -> &[u16; PULSE_LEN] {
+        self.tick = self.tick.wrapping_add(1);
+        let tick = self.tick;
+        let found = self
+            .entries
+            .iter()
+            .position(|slot| matches!(slot, Some(e) if e.message == *message));
+        let index = found.unwrap_or_else(|| {
+            // Empty slots count as the oldest.
+            let age = |i: &usize| {
+                self.entries[*i]
+                    .as_ref()
+                    .map_or(u32::MAX, |e| tick.wrapping_sub(e.used))
+            };
+            let oldest = (0..N).max_by_key(age).unwrap_or(0);
+            self.entries[oldest] = None;
+            oldest
+        });
+        let entry = self.entries[index].get_or_insert_with(|| Entry {
+            message: *message,
+            pulses: message.encode_pulses(),
+            used: tick,
+        });
+        entry.used = tick;
+        &entry.pulses
+    }
+
+    /// Whether the pulses of `message` are cached.
+    pub fn contains(&self, message: &Message) -> bool {
+        self.entries.iter().flatten().any(|e| e.message == *message)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.entries = [const { None }; N];
+    }
+}
+
+impl<const N: usize> Default for FrameCache<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
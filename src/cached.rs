@@ -0,0 +1,109 @@
+use crate::{Fan, Message, Mode, SwingMode, Temperature};
+
+/// A [`Message`] that keeps the sum behind its checksum, so the setters below only
+/// account for the byte they change instead of summing the whole block again.
+///
+/// Meant for state that changes many times per frame sent, e.g. a slider previewing
+/// frames. Settings without a setter here go through [`CachedMessage::modify`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct CachedMessage {
+    message: Message,
+    /// The nibbles the checksum covers, summed without reducing.
+    sum: u8,
+}
+
+impl CachedMessage {
+    pub fn new(message: Message) -> Self {
+        let mut cached = Self { message, sum: 0 };
+        cached.resync();
+        cached
+    }
+
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    pub fn into_message(self) -> Message {
+        self.message
+    }
+
+    /// Changes the message with any of its own setters and sums the checksum again.
+    pub fn modify(&mut self, f: impl FnOnce(&mut Message)) {
+        f(&mut self.message);
+        self.resync();
+    }
+
+    pub fn set_mode(&mut self, mode: Mode) {
+        let state = self.message.remote_state;
+        self.write(0, state[0] & 0b1111_1000 | mode.encode());
+        if !matches!(mode, Mode::Cold) {
+            // Same as `Message::set_mode`, SE is dropped outside cool mode.
+            self.write(7, state[7] & 0b1111_1101);
+        }
+    }
+
+    pub fn set_on(&mut self, on: bool) {
+        let byte = self.message.remote_state[0];
+        self.write(0, byte & 0b1111_0111 | (on as u8) << 3);
+    }
+
+    pub fn set_fan(&mut self, fan: Fan) {
+        let byte = self.message.remote_state[0];
+        self.write(0, byte & 0b1100_1111 | fan.encode() << 4);
+    }
+
+    /// See [`Message::set_temperature`].
+    pub fn set_temperature(&mut self, temp: Temperature) {
+        let [byte1, byte3] = self.message.temperature_bytes(temp);
+        self.write(1, byte1);
+        self.write(3, byte3);
+    }
+
+    pub fn set_v_swing(&mut self, mode: SwingMode) {
+        let byte = self.message.remote_state[4];
+        self.write(4, byte & 0xF0 | mode.encode());
+    }
+
+    pub fn set_h_swing(&mut self, mode: SwingMode) {
+        let byte = self.message.remote_state[4];
+        self.write(4, byte & 0x0F | mode.encode() << 4);
+    }
+
+    /// Replaces byte `index` of the state and moves the checksum by the difference.
+    fn write(&mut self, index: usize, byte: u8) {
+        let state = &mut self.message.remote_state;
+        self.sum = self
+            .sum
+            .wrapping_sub(covered(index, state[index]))
+            .wrapping_add(covered(index, byte));
+        state[index] = byte;
+        state[7] = state[7] & 0x0F | (10u8.wrapping_add(self.sum) & 0xF) << 4;
+    }
+
+    fn resync(&mut self) {
+        let state = &self.message.remote_state;
+        self.sum = (0..7).fold(0u8, |sum, i| sum.wrapping_add(covered(i, state[i])));
+    }
+}
+
+impl From<Message> for CachedMessage {
+    fn from(message: Message) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<CachedMessage> for Message {
+    fn from(cached: CachedMessage) -> Self {
+        cached.message
+    }
+}
+
+/// The nibble of byte `index` that [`checksum_block`](crate::checksum_block) adds up.
+fn covered(index: usize, byte: u8) -> u8 {
+    match index {
+        0..4 => byte & 0xF,
+        4..7 => byte >> 4,
+        _ => 0,
+    }
+}
@@ -0,0 +1,38 @@
+use clap::{builder::PossibleValue, ValueEnum};
+
+use crate::{
+    parse::{FAN_NAMES, MODE_NAMES, SWING_MODE_NAMES, TEMPERATURE_DISPLAY_NAMES},
+    Fan, Mode, SwingMode, TemperatureDisplay,
+};
+
+/// Lists the names [`FromStr`](core::str::FromStr) accepts, the first one as the value and
+/// the others as aliases.
+macro_rules! impl_value_enum {
+    ($ty:ident, $names:ident) => {
+        impl ValueEnum for $ty {
+            fn value_variants<'a>() -> &'a [Self] {
+                const VARIANTS: [$ty; $names.len()] = {
+                    let mut variants = [$names[0].1; $names.len()];
+                    let mut i = 0;
+                    while i < $names.len() {
+                        variants[i] = $names[i].1;
+                        i += 1;
+                    }
+                    variants
+                };
+                &VARIANTS
+            }
+
+            fn to_possible_value(&self) -> Option<PossibleValue> {
+                let (names, _) = $names.iter().find(|(_, value)| value == self)?;
+                let (name, aliases) = names.split_first()?;
+                Some(PossibleValue::new(*name).aliases(aliases.iter().copied()))
+            }
+        }
+    };
+}
+
+impl_value_enum!(Mode, MODE_NAMES);
+impl_value_enum!(Fan, FAN_NAMES);
+impl_value_enum!(SwingMode, SWING_MODE_NAMES);
+impl_value_enum!(TemperatureDisplay, TEMPERATURE_DISPLAY_NAMES);
@@ -0,0 +1,49 @@
+use crate::{DecodeError, Fan, Mode, SwingMode, TemperatureDisplay};
+
+/// Fails for values the mode bits can't hold or that name no mode.
+impl TryFrom<u8> for Mode {
+    type Error = DecodeError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if value >> 3 != 0 {
+            return Err(DecodeError::InvalidMode);
+        }
+        Mode::decode(value)
+    }
+}
+
+impl From<Mode> for u8 {
+    fn from(mode: Mode) -> u8 {
+        mode.encode()
+    }
+}
+
+macro_rules! impl_raw {
+    ($ty:ident, $width:literal, $error:expr) => {
+        /// Fails for values wider than the field, where `decode` would mask them.
+        impl TryFrom<u8> for $ty {
+            type Error = DecodeError;
+
+            fn try_from(value: u8) -> Result<Self, Self::Error> {
+                if value >> $width != 0 {
+                    return Err($error);
+                }
+                Ok($ty::decode(value))
+            }
+        }
+
+        impl From<$ty> for u8 {
+            fn from(value: $ty) -> u8 {
+                value.encode()
+            }
+        }
+    };
+}
+
+impl_raw!(Fan, 2, DecodeError::InvalidFan);
+impl_raw!(SwingMode, 4, DecodeError::InvalidSwingMode);
+impl_raw!(
+    TemperatureDisplay,
+    2,
+    DecodeError::InvalidTemperatureDisplay
+);
@@ -0,0 +1,72 @@
+//! Deliberately broken frames for exercising receivers' error paths.
+
+use crate::{Code, RawFrame, FRAME_LEN};
+
+/// A way to break a valid frame.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum Corruption {
+    /// Flip the data bit at this code position.
+    FlipBit(usize),
+    /// Flip the lowest bit of the checksum nibble.
+    WrongChecksum,
+    /// Flip the middle code of the magic between the blocks.
+    WrongMagic,
+    /// Keep only this many codes.
+    Truncate(usize),
+}
+
+/// A corrupted frame, only the first `len` codes are meaningful.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct Corrupted {
+    pub codes: [Code; FRAME_LEN],
+    pub len: usize,
+}
+
+impl Corrupted {
+    pub fn as_slice(&self) -> &[Code] {
+        &self.codes[..self.len]
+    }
+}
+
+impl Corruption {
+    /// Every corruption: each data bit flipped, a wrong checksum, a wrong magic and
+    /// every possible truncation.
+    pub fn all() -> impl Iterator<Item = Corruption> + Clone {
+        (0..FRAME_LEN)
+            .filter(|&pos| is_data(pos))
+            .map(Corruption::FlipBit)
+            .chain([Corruption::WrongChecksum, Corruption::WrongMagic])
+            .chain((0..FRAME_LEN).map(Corruption::Truncate))
+    }
+
+    /// Applies the corruption to a copy of `frame`.
+    ///
+    /// Flipping a marker position or truncating to more than [`FRAME_LEN`] codes leaves the
+    /// frame unchanged.
+    pub fn apply(&self, frame: &RawFrame) -> Corrupted {
+        let mut codes = frame.0;
+        let mut len = FRAME_LEN;
+        match *self {
+            Corruption::FlipBit(pos) if pos < FRAME_LEN && is_data(pos) => flip(&mut codes[pos]),
+            Corruption::FlipBit(_) => {}
+            Corruption::WrongChecksum => flip(&mut codes[65]),
+            Corruption::WrongMagic => flip(&mut codes[34]),
+            Corruption::Truncate(n) => len = n.min(FRAME_LEN),
+        }
+        Corrupted { codes, len }
+    }
+}
+
+fn is_data(pos: usize) -> bool {
+    !matches!(pos, 0 | 36 | 69)
+}
+
+fn flip(code: &mut Code) {
+    *code = match *code {
+        Code::Short => Code::Long,
+        Code::Long => Code::Short,
+        other => other,
+    };
+}
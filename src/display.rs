@@ -0,0 +1,201 @@
+use core::fmt::{Display, Formatter, Result};
+
+#[cfg(feature = "decode")]
+use crate::LocatedError;
+use crate::{
+    DecodeError, EncodeError, Fan, FieldValue, Message, Mode, SwingMode, Temperature,
+    TemperatureDisplay, TimerSetting, UnitId,
+};
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(match self {
+            Mode::Auto => "Auto",
+            Mode::Cold => "Cool",
+            Mode::Dry => "Dry",
+            Mode::Wind => "Fan",
+            Mode::Hot => "Heat",
+        })
+    }
+}
+
+impl Display for Fan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(match self {
+            Fan::Auto => "auto",
+            Fan::Level1 => "1",
+            Fan::Level2 => "2",
+            Fan::Level3 => "3",
+        })
+    }
+}
+
+impl Display for Temperature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Temperature::Centigrade(degree) => write!(f, "{}℃", degree),
+            Temperature::Fahrenheit(degree) => write!(f, "{}℉", degree),
+        }
+    }
+}
+
+impl Display for SwingMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            SwingMode::Off => f.write_str("off"),
+            SwingMode::On => f.write_str("on"),
+            other => write!(f, "{}", *other as u8),
+        }
+    }
+}
+
+impl Display for TemperatureDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(match self {
+            TemperatureDisplay::Setting => "setting",
+            TemperatureDisplay::Room => "room",
+            TemperatureDisplay::Indoor => "indoor",
+            TemperatureDisplay::Outdoor => "outdoor",
+        })
+    }
+}
+
+/// Renders as `7.5 h (enabled)`, or `off` for a disabled timer.
+impl Display for TimerSetting {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if !self.enabled {
+            return f.write_str("off");
+        }
+        write_hours(f, self.half_hours)?;
+        f.write_str(" (enabled)")
+    }
+}
+
+impl Display for UnitId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.get().fmt(f)
+    }
+}
+
+fn write_hours(f: &mut Formatter<'_>, half_hours: u8) -> Result {
+    if half_hours & 1 != 0 {
+        write!(f, "{}.5 h", half_hours / 2)
+    } else {
+        write!(f, "{} h", half_hours / 2)
+    }
+}
+
+impl Display for FieldValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            FieldValue::Bool(true) => f.write_str("on"),
+            FieldValue::Bool(false) => f.write_str("off"),
+            FieldValue::Mode(mode) => mode.fmt(f),
+            FieldValue::Fan(fan) => fan.fmt(f),
+            FieldValue::Temperature(temp) => temp.fmt(f),
+            FieldValue::Timer(timer) => timer.fmt(f),
+            FieldValue::Swing(swing) => swing.fmt(f),
+            FieldValue::TemperatureDisplay(temp_display) => temp_display.fmt(f),
+            FieldValue::UnitId(Some(id)) => id.fmt(f),
+            FieldValue::UnitId(None) => f.write_str("any"),
+            FieldValue::Invalid => f.write_str("invalid"),
+        }
+    }
+}
+
+/// A compact one-line summary, e.g. `On Cool 24℃ fan=auto swing=off timer=7.5 h light`.
+///
+/// Flags are only listed when they are enabled.
+impl Display for Message {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(if self.is_on() { "On " } else { "Off " })?;
+        match self.mode() {
+            Ok(mode) => write!(f, "{}", mode)?,
+            Err(_) => f.write_str("?")?,
+        }
+        match self.temperature() {
+            Ok(temp) => write!(f, " {}", temp)?,
+            Err(_) => f.write_str(" ?")?,
+        }
+        write!(f, " fan={} swing={}", self.fan(), self.v_swing())?;
+        match self.timer() {
+            Ok(timer) if timer.enabled => {
+                f.write_str(" timer=")?;
+                write_hours(f, timer.half_hours)?;
+            }
+            Ok(_) => f.write_str(" timer=off")?,
+            Err(_) => f.write_str(" timer=?")?,
+        }
+        let flags = [
+            (self.swing(), "auto-swing"),
+            (self.sleep(), "sleep"),
+            (self.turbo(), "turbo"),
+            (self.light(), "light"),
+            (self.health(), "health"),
+            (self.dry(), "dry"),
+            (self.ventilate(), "ventilate"),
+            (self.i_feel(), "i-feel"),
+            (self.wifi(), "wifi"),
+            (self.econo(), "econo"),
+            (self.se(), "se"),
+        ];
+        for (_, name) in flags.iter().filter(|(enabled, _)| *enabled) {
+            write!(f, " {}", name)?;
+        }
+        if let Some(id) = self.unit_id() {
+            write!(f, " unit={}", id)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(match self {
+            DecodeError::InvalidMarker => "marker missing or in the wrong place",
+            DecodeError::UnexpectedMarker => "marker where a data bit was expected",
+            DecodeError::InvalidCode => "not a valid code",
+            DecodeError::InvalidMode => "invalid mode",
+            DecodeError::InvalidTimerSetting => "invalid timer setting",
+            DecodeError::InvalidTimerStep => "timer is not a step the remote can set",
+            DecodeError::InvalidFan => "invalid fan speed",
+            DecodeError::InvalidTemperature => "temperature out of range",
+            DecodeError::InvalidSwingMode => "invalid swing mode",
+            DecodeError::InvalidTemperatureDisplay => "invalid temperature display",
+            DecodeError::InvalidUnitId => "unit ID doesn't fit into 4 bits",
+            DecodeError::InvalidMagic => "magic between the blocks doesn't match",
+            DecodeError::InvalidVersion => "unknown layout version",
+            DecodeError::Eof => "frame ended early",
+            DecodeError::Checksum {
+                expected,
+                found,
+                block,
+            } => {
+                return write!(
+                    f,
+                    "checksum mismatch: expected {:#x}, found {:#x} over {:02x?}",
+                    expected, found, block
+                )
+            }
+        })
+    }
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(match self {
+            EncodeError::InvalidMode => "invalid mode",
+            EncodeError::InvalidTimerSetting => "timer can't be encoded",
+            EncodeError::InvalidTemperature => "temperature out of range",
+            EncodeError::EconoOutsideCool => "econo is only honoured in cool mode",
+            EncodeError::UnsupportedSwing => "swing not supported by the model",
+        })
+    }
+}
+
+#[cfg(feature = "decode")]
+impl Display for LocatedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} at code {}", self.error, self.index)
+    }
+}
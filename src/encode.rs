@@ -0,0 +1,115 @@
+#[cfg(feature = "encode")]
+use core::iter::FusedIterator;
+
+#[cfg(feature = "encode")]
+use crate::{Code, Message, MAGIC_3};
+
+/// Number of codes in a frame.
+pub const FRAME_LEN: usize = 70;
+
+/// The codes of a frame, see [`Message::encode`](crate::Message::encode).
+///
+/// It owns the encoded frame, so it can be stored and resumed at any time.
+#[cfg(feature = "encode")]
+#[derive(Clone)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct EncodeIter {
+    codes: [Code; FRAME_LEN],
+    pos: u8,
+}
+
+#[cfg(feature = "encode")]
+impl EncodeIter {
+    pub(crate) fn new(state: [u8; 8]) -> Self {
+        Self {
+            codes: encode_frame(&state),
+            pos: 0,
+        }
+    }
+}
+
+/// Lays out the whole frame with plain loops, which [`EncodeIter`],
+/// [`Message::encode_into`] and [`Message::encode_frame`] share.
+///
+/// Kept `const` so fixed frames can be computed at compile time.
+#[cfg(feature = "encode")]
+const fn encode_frame(state: &[u8; 8]) -> [Code; FRAME_LEN] {
+    let mut codes = [Code::Short; FRAME_LEN];
+    codes[0] = Code::Start;
+    codes[33] = MAGIC_3[0];
+    codes[34] = MAGIC_3[1];
+    codes[35] = MAGIC_3[2];
+    codes[36] = Code::Continue;
+    codes[69] = Code::End;
+    let mut bit = 0;
+    while bit < 64 {
+        // The magic and the continue marker sit between the two blocks.
+        let index = if bit < 32 { bit + 1 } else { bit + 5 };
+        if state[bit / 8] >> (bit % 8) & 1 != 0 {
+            codes[index] = Code::Long;
+        }
+        bit += 1;
+    }
+    codes
+}
+
+#[cfg(feature = "encode")]
+impl Iterator for EncodeIter {
+    type Item = Code;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let code = *self.codes.get(self.pos as usize)?;
+        self.pos += 1;
+        Some(code)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = FRAME_LEN - self.pos as usize;
+        (len, Some(len))
+    }
+}
+
+#[cfg(feature = "encode")]
+impl ExactSizeIterator for EncodeIter {}
+
+#[cfg(feature = "encode")]
+impl FusedIterator for EncodeIter {}
+
+#[cfg(feature = "encode")]
+impl Message {
+    /// The whole frame as an array, usable in constants:
+    ///
+    /// ```
+    /// use gree_ir::{Code, Message, FRAME_LEN};
+    ///
+    /// static POWER_OFF: [Code; FRAME_LEN] = Message::new().encode_frame();
+    /// ```
+    pub const fn encode_frame(&self) -> [Code; FRAME_LEN] {
+        encode_frame(&self.remote_state)
+    }
+
+    /// Writes the whole frame into `buf` and returns it as a slice.
+    pub fn encode_into<'a>(&self, buf: &'a mut [Code; FRAME_LEN]) -> &'a [Code] {
+        *buf = encode_frame(&self.remote_state);
+        buf
+    }
+}
+
+#[cfg(all(feature = "encode", feature = "alloc"))]
+impl Message {
+    /// [`Message::encode`] with the iterator type erased, for collections that hold frames
+    /// of several kinds.
+    pub fn encode_boxed(&self) -> alloc::boxed::Box<dyn Iterator<Item = Code>> {
+        alloc::boxed::Box::new(self.encode())
+    }
+}
+
+#[cfg(feature = "encode")]
+impl IntoIterator for &Message {
+    type Item = Code;
+    type IntoIter = EncodeIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.encode()
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{Code, Field, Message, Model, MAGIC_3};
+
+/// What one code of a frame means, see [`explain`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct Annotation {
+    pub index: usize,
+    pub code: Code,
+    /// The field the code belongs to, or `start`, `magic`, `continue`, `end`, `checksum`
+    /// and `reserved`.
+    pub label: &'static str,
+    pub meaning: String,
+}
+
+/// Maps every code of a capture to the field it belongs to and how it is interpreted.
+///
+/// Works on captures that don't decode: misplaced markers and bad checksums are
+/// reported in the meaning column instead of failing.
+pub fn explain(codes: &[Code; 70]) -> Vec<Annotation> {
+    // Best effort state, markers where data is expected count as zero.
+    let mut message = Message {
+        remote_state: [0; 8],
+        model: Model::DEFAULT,
+    };
+    for (index, code) in codes.iter().enumerate() {
+        if let (Some(bit), Code::Long) = (state_bit(index), code) {
+            message.remote_state[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    let expect = |expected: Code, code: Code| {
+        if expected == code {
+            String::from("ok")
+        } else {
+            format!("expected {:?}", expected)
+        }
+    };
+    codes
+        .iter()
+        .enumerate()
+        .map(|(index, &code)| {
+            let (label, meaning) = match state_bit(index) {
+                None => match index {
+                    0 => ("start", expect(Code::Start, code)),
+                    36 => ("continue", expect(Code::Continue, code)),
+                    69 => ("end", expect(Code::End, code)),
+                    _ => ("magic", expect(MAGIC_3[index - 33], code)),
+                },
+                Some(_) if !matches!(code, Code::Short | Code::Long) => {
+                    ("data", format!("expected data, found {:?}", code))
+                }
+                Some(60..=63) => {
+                    let expected = message.checksum();
+                    let found = message.remote_state[7] >> 4;
+                    let meaning = if expected == found {
+                        format!("{:#x}, ok", found)
+                    } else {
+                        format!("{:#x}, expected {:#x}", found, expected)
+                    };
+                    ("checksum", meaning)
+                }
+                Some(bit) => match field_of(bit as u8) {
+                    Some(field) => (field.name(), format!("{}", message.field(field))),
+                    None => (
+                        "reserved",
+                        String::from(if code == Code::Long { "1" } else { "0" }),
+                    ),
+                },
+            };
+            Annotation {
+                index,
+                code,
+                label,
+                meaning,
+            }
+        })
+        .collect()
+}
+
+/// The bit of the state carried at a code position, `None` for markers and magic.
+fn state_bit(index: usize) -> Option<usize> {
+    match index {
+        1..=32 => Some(index - 1),
+        37..=68 => Some(index - 37 + 32),
+        _ => None,
+    }
+}
+
+fn field_of(bit: u8) -> Option<Field> {
+    Field::ALL
+        .into_iter()
+        .find(|field| field.bits().iter().any(|range| range.contains(&bit)))
+}
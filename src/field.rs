@@ -0,0 +1,324 @@
+use core::ops::Range;
+
+use crate::{Fan, Message, Mode, SwingMode, Temperature, TemperatureDisplay, TimerSetting, UnitId};
+
+/// A setting carried by a [`Message`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[non_exhaustive]
+pub enum Field {
+    Mode,
+    On,
+    Fan,
+    Swing,
+    Sleep,
+    Temperature,
+    Timer,
+    Turbo,
+    Light,
+    Health,
+    Dry,
+    Ventilate,
+    VSwing,
+    HSwing,
+    TemperatureDisplay,
+    IFeel,
+    Wifi,
+    UnitId,
+    Se,
+    Econo,
+}
+
+impl Field {
+    /// Every field, in the order they appear in the frame.
+    pub const ALL: [Field; 20] = [
+        Field::Mode,
+        Field::On,
+        Field::Fan,
+        Field::Swing,
+        Field::Sleep,
+        Field::Temperature,
+        Field::Timer,
+        Field::Turbo,
+        Field::Light,
+        Field::Health,
+        Field::Dry,
+        Field::Ventilate,
+        Field::VSwing,
+        Field::HSwing,
+        Field::TemperatureDisplay,
+        Field::IFeel,
+        Field::Wifi,
+        Field::UnitId,
+        Field::Se,
+        Field::Econo,
+    ];
+
+    /// The name of the field, the same as its getter on [`Message`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Mode => "mode",
+            Field::On => "on",
+            Field::Fan => "fan",
+            Field::Swing => "swing",
+            Field::Sleep => "sleep",
+            Field::Temperature => "temperature",
+            Field::Timer => "timer",
+            Field::Turbo => "turbo",
+            Field::Light => "light",
+            Field::Health => "health",
+            Field::Dry => "dry",
+            Field::Ventilate => "ventilate",
+            Field::VSwing => "v_swing",
+            Field::HSwing => "h_swing",
+            Field::TemperatureDisplay => "temperature_display",
+            Field::IFeel => "i_feel",
+            Field::Wifi => "wifi",
+            Field::UnitId => "unit_id",
+            Field::Econo => "econo",
+            Field::Se => "se",
+        }
+    }
+
+    /// Looks a field up by its [name](Field::name).
+    pub fn from_name(name: &str) -> Option<Field> {
+        Field::ALL.into_iter().find(|field| field.name() == name)
+    }
+
+    /// Whether the field only changes what the indoor unit shows, not how it runs.
+    pub fn is_cosmetic(&self) -> bool {
+        matches!(self, Field::Light | Field::TemperatureDisplay)
+    }
+
+    /// The bits of [`Message::raw`] holding the field, numbered LSB first from the
+    /// first byte, i.e. in transmission order.
+    ///
+    /// Most fields are one contiguous range, the temperature has its unit and extra
+    /// degree bits in a second one and the unit ID its presence flag.
+    #[allow(clippy::single_range_in_vec_init)]
+    pub fn bits(&self) -> &'static [Range<u8>] {
+        match self {
+            Field::Mode => &[0..3],
+            Field::On => &[3..4],
+            Field::Fan => &[4..6],
+            Field::Swing => &[6..7],
+            Field::Sleep => &[7..8],
+            Field::Temperature => &[8..12, 26..28],
+            Field::Timer => &[12..20],
+            Field::Turbo => &[20..21],
+            Field::Light => &[21..22],
+            Field::Health => &[22..23],
+            Field::Dry => &[23..24],
+            Field::Ventilate => &[24..25],
+            Field::VSwing => &[32..36],
+            Field::HSwing => &[36..40],
+            Field::TemperatureDisplay => &[40..42],
+            Field::IFeel => &[42..43],
+            Field::Wifi => &[46..47],
+            Field::UnitId => &[48..52, 55..56],
+            Field::Se => &[57..58],
+            Field::Econo => &[58..59],
+        }
+    }
+}
+
+/// The value of a [`Field`], `Invalid` if the bits don't decode.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[non_exhaustive]
+pub enum FieldValue {
+    Bool(bool),
+    Mode(Mode),
+    Fan(Fan),
+    Temperature(Temperature),
+    Timer(TimerSetting),
+    Swing(SwingMode),
+    TemperatureDisplay(TemperatureDisplay),
+    UnitId(Option<UnitId>),
+    Invalid,
+}
+
+/// Why [`Message::set`] failed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[non_exhaustive]
+pub enum FieldError {
+    UnknownField,
+    /// The value is of another type than the field, or can't be encoded.
+    InvalidValue,
+}
+
+/// A field whose value differs between two messages.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct Change {
+    pub field: Field,
+    pub old: FieldValue,
+    pub new: FieldValue,
+}
+
+/// Iterator over the [`Change`]s between two messages, see [`Message::diff`].
+#[derive(Clone)]
+pub struct Diff<'a> {
+    old: &'a Message,
+    new: &'a Message,
+    fields: core::slice::Iter<'static, Field>,
+}
+
+impl Iterator for Diff<'_> {
+    type Item = Change;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.fields.find_map(|&field| {
+            let old = self.old.field(field);
+            let new = self.new.field(field);
+            (old != new).then_some(Change { field, old, new })
+        })
+    }
+}
+
+/// One row of [`Message::fields`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct FieldInfo {
+    pub field: Field,
+    pub name: &'static str,
+    pub bits: &'static [Range<u8>],
+    /// The field's bits concatenated, the first range in the lowest bits.
+    pub raw: u16,
+    pub value: FieldValue,
+}
+
+/// Iterator over every field of a message, see [`Message::fields`].
+#[derive(Clone)]
+pub struct Fields<'a> {
+    message: &'a Message,
+    fields: core::slice::Iter<'static, Field>,
+}
+
+impl Iterator for Fields<'_> {
+    type Item = FieldInfo;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let field = *self.fields.next()?;
+        Some(FieldInfo {
+            field,
+            name: field.name(),
+            bits: field.bits(),
+            raw: self.message.raw_bits(field.bits()),
+            value: self.message.field(field),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.fields.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Fields<'_> {}
+
+impl Message {
+    /// Describes every field, so tools can render a frame without knowing its layout.
+    pub fn fields(&self) -> Fields<'_> {
+        Fields {
+            message: self,
+            fields: Field::ALL.iter(),
+        }
+    }
+
+    fn raw_bits(&self, ranges: &[Range<u8>]) -> u16 {
+        let mut raw = 0;
+        for (width, bit) in ranges.iter().cloned().flatten().enumerate() {
+            let value = self.remote_state[bit as usize / 8] >> (bit % 8) & 1;
+            raw |= (value as u16) << width;
+        }
+        raw
+    }
+
+    pub fn field(&self, field: Field) -> FieldValue {
+        match field {
+            Field::Mode => self.mode().map_or(FieldValue::Invalid, FieldValue::Mode),
+            Field::On => FieldValue::Bool(self.is_on()),
+            Field::Fan => FieldValue::Fan(self.fan()),
+            Field::Swing => FieldValue::Bool(self.swing()),
+            Field::Sleep => FieldValue::Bool(self.sleep()),
+            Field::Temperature => self
+                .temperature()
+                .map_or(FieldValue::Invalid, FieldValue::Temperature),
+            Field::Timer => self.timer().map_or(FieldValue::Invalid, FieldValue::Timer),
+            Field::Turbo => FieldValue::Bool(self.turbo()),
+            Field::Light => FieldValue::Bool(self.light()),
+            Field::Health => FieldValue::Bool(self.health()),
+            Field::Dry => FieldValue::Bool(self.dry()),
+            Field::Ventilate => FieldValue::Bool(self.ventilate()),
+            Field::VSwing => FieldValue::Swing(self.v_swing()),
+            Field::HSwing => FieldValue::Swing(self.h_swing()),
+            Field::TemperatureDisplay => FieldValue::TemperatureDisplay(self.temperature_display()),
+            Field::IFeel => FieldValue::Bool(self.i_feel()),
+            Field::Wifi => FieldValue::Bool(self.wifi()),
+            Field::UnitId => FieldValue::UnitId(self.unit_id()),
+            Field::Econo => FieldValue::Bool(self.econo()),
+            Field::Se => FieldValue::Bool(self.se()),
+        }
+    }
+
+    /// Reads a field by name, e.g. `msg.get("fan")`.
+    pub fn get(&self, name: &str) -> Option<FieldValue> {
+        Field::from_name(name).map(|field| self.field(field))
+    }
+
+    /// Writes a field by name, e.g. `msg.set("fan", FieldValue::Fan(Fan::Level2))`.
+    pub fn set(&mut self, name: &str, value: FieldValue) -> Result<(), FieldError> {
+        let field = Field::from_name(name).ok_or(FieldError::UnknownField)?;
+        self.set_field(field, value)
+    }
+
+    pub fn set_field(&mut self, field: Field, value: FieldValue) -> Result<(), FieldError> {
+        match (field, value) {
+            (Field::Mode, FieldValue::Mode(mode)) => self.set_mode(mode),
+            (Field::On, FieldValue::Bool(on)) => self.set_on(on),
+            (Field::Fan, FieldValue::Fan(fan)) => self.set_fan(fan),
+            (Field::Swing, FieldValue::Bool(swing)) => self.set_swing(swing),
+            (Field::Sleep, FieldValue::Bool(sleep)) => self.set_sleep(sleep),
+            (Field::Temperature, FieldValue::Temperature(temp)) => self.set_temperature(temp),
+            (Field::Timer, FieldValue::Timer(setting)) => self
+                .set_timer(&setting)
+                .map_err(|_| FieldError::InvalidValue)?,
+            (Field::Turbo, FieldValue::Bool(turbo)) => self.set_turbo(turbo),
+            (Field::Light, FieldValue::Bool(light)) => self.set_light(light),
+            (Field::Health, FieldValue::Bool(health)) => self.set_health(health),
+            (Field::Dry, FieldValue::Bool(dry)) => self.set_dry(dry),
+            (Field::Ventilate, FieldValue::Bool(ventilate)) => self.set_ventilateo(ventilate),
+            (Field::VSwing, FieldValue::Swing(mode)) => self.set_v_swing(mode),
+            (Field::HSwing, FieldValue::Swing(mode)) => self.set_h_swing(mode),
+            (Field::TemperatureDisplay, FieldValue::TemperatureDisplay(temp_display)) => {
+                self.set_temperature_display(temp_display)
+            }
+            (Field::IFeel, FieldValue::Bool(i_feel)) => self.set_i_feel(i_feel),
+            (Field::Wifi, FieldValue::Bool(wifi)) => self.set_wifi(wifi),
+            (Field::UnitId, FieldValue::UnitId(unit_id)) => self.set_unit_id(unit_id),
+            (Field::Se, FieldValue::Bool(se)) => self.set_se(se),
+            (Field::Econo, FieldValue::Bool(econo)) => self.set_econo(econo),
+            _ => return Err(FieldError::InvalidValue),
+        }
+        Ok(())
+    }
+
+    /// Whether both messages make the AC run the same way, ignoring
+    /// [cosmetic](Field::is_cosmetic) fields, reserved bits and the model.
+    pub fn is_equivalent(&self, other: &Message) -> bool {
+        Field::ALL
+            .iter()
+            .filter(|field| !field.is_cosmetic())
+            .all(|field| self.raw_bits(field.bits()) == other.raw_bits(field.bits()))
+    }
+
+    /// Lists the fields that changed from `self` to `other`.
+    pub fn diff<'a>(&'a self, other: &'a Message) -> Diff<'a> {
+        Diff {
+            old: self,
+            new: other,
+            fields: Field::ALL.iter(),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use core::ops::Range;
+
+#[cfg(feature = "decode")]
+use crate::DecodeError;
+#[cfg(any(feature = "encode", feature = "decode"))]
+use crate::Message;
+use crate::{checksum_block, Code, MAGIC_3};
+
+/// The exact codes of a frame, including the bits nobody understands yet.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct RawFrame(pub [Code; 70]);
+
+impl RawFrame {
+    #[cfg(feature = "encode")]
+    pub fn from_message(message: &Message) -> Self {
+        let mut codes = [Code::Short; 70];
+        message.encode_into(&mut codes);
+        Self(codes)
+    }
+
+    #[cfg(feature = "decode")]
+    pub fn to_message(&self) -> Result<Message, DecodeError> {
+        Message::decode(&self.0)
+    }
+
+    pub fn view(&self) -> &[Code; 70] {
+        &self.0
+    }
+
+    pub fn view_mut(&mut self) -> &mut [Code; 70] {
+        &mut self.0
+    }
+
+    /// Rewrites the markers, the magic and the checksum to match the data bits, so the AC
+    /// accepts a frame again after its bits were edited by hand.
+    ///
+    /// Markers found where data is expected are read as 0 bits.
+    pub fn repair(&mut self) {
+        let codes = &mut self.0;
+        codes[0] = Code::Start;
+        codes[33..36].copy_from_slice(&MAGIC_3);
+        codes[36] = Code::Continue;
+        codes[69] = Code::End;
+        let mut state = [0; 8];
+        for bit in 0..64 {
+            let code = &mut codes[code_index(bit)];
+            if *code == Code::Long {
+                state[bit / 8] |= 1 << (bit % 8);
+            } else {
+                *code = Code::Short;
+            }
+        }
+        self.write_checksum(&state);
+    }
+
+    /// Reads the state bits in `bits`, numbered like [`Field::bits`](crate::Field::bits),
+    /// into the low bits of the result. Bits past the 64 state bits read as 0, and so do
+    /// markers.
+    pub fn get_bits(&self, bits: Range<u8>) -> u64 {
+        let mut value = 0;
+        for (i, bit) in bits.filter(|&bit| bit < 64).enumerate() {
+            value |= ((self.0[code_index(bit as usize)] == Code::Long) as u64) << i;
+        }
+        value
+    }
+
+    /// Writes the low bits of `value` into the state bits in `bits` and recomputes the
+    /// checksum, so any bit can be explored without breaking the frame.
+    ///
+    /// Bits past the 64 state bits are ignored, and writing the checksum bits has no
+    /// effect.
+    pub fn set_bits(&mut self, bits: Range<u8>, value: u64) {
+        for (i, bit) in bits.filter(|&bit| bit < 64).enumerate() {
+            self.0[code_index(bit as usize)] = Code::from(value >> i & 1 != 0);
+        }
+        let state = self.get_bits(0..64).to_le_bytes();
+        self.write_checksum(&state);
+    }
+
+    fn write_checksum(&mut self, state: &[u8; 8]) {
+        let checksum = checksum_block(state);
+        for i in 0..4 {
+            self.0[code_index(60 + i)] = Code::from(checksum >> i & 1 != 0);
+        }
+    }
+}
+
+impl From<[Code; 70]> for RawFrame {
+    fn from(codes: [Code; 70]) -> Self {
+        Self(codes)
+    }
+}
+
+#[cfg(feature = "encode")]
+impl From<&Message> for RawFrame {
+    fn from(message: &Message) -> Self {
+        Self::from_message(message)
+    }
+}
+
+/// The position in the frame of a bit of [`Message::raw`](crate::Message::raw), numbered LSB first.
+fn code_index(bit: usize) -> usize {
+    match bit {
+        0..32 => bit + 1,
+        _ => bit + 5,
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{Message, Mode, SwingMode};
+
+/// A key on the physical remote.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[non_exhaustive]
+pub enum Key {
+    Power,
+    TempUp,
+    TempDown,
+    Mode,
+    Fan,
+    Swing,
+    Turbo,
+    Light,
+    Sleep,
+    Health,
+    Dry,
+    IFeel,
+}
+
+impl Message {
+    /// Changes the state the way pressing `key` on the remote does.
+    pub fn press(&mut self, key: Key) {
+        match key {
+            Key::Power => self.toggle_power(),
+            // The setpoint is fixed in auto mode and unused in fan mode.
+            Key::TempUp | Key::TempDown if matches!(self.mode(), Ok(Mode::Auto | Mode::Wind)) => {}
+            Key::TempUp => self.temperature_up(),
+            Key::TempDown => self.temperature_down(),
+            Key::Mode => self.cycle_mode(),
+            // The fan speed is locked in dry mode.
+            Key::Fan if matches!(self.mode(), Ok(Mode::Dry)) => {}
+            Key::Fan => self.cycle_fan(),
+            Key::Swing => {
+                let swing = !self.swing();
+                self.set_swing(swing);
+                self.set_v_swing(if swing { SwingMode::On } else { SwingMode::Off });
+            }
+            // Turbo is not available in dry and fan mode.
+            Key::Turbo if matches!(self.mode(), Ok(Mode::Dry | Mode::Wind)) => {}
+            // Turbo and sleep exclude each other, turning one on turns the other off.
+            Key::Turbo => {
+                let turbo = !self.turbo();
+                self.set_turbo(turbo);
+                if turbo {
+                    self.set_sleep(false);
+                }
+            }
+            Key::Light => self.set_light(!self.light()),
+            Key::Sleep => {
+                let sleep = !self.sleep();
+                self.set_sleep(sleep);
+                if sleep {
+                    self.set_turbo(false);
+                }
+            }
+            Key::Health => self.set_health(!self.health()),
+            Key::Dry => self.set_dry(!self.dry()),
+            Key::IFeel => self.set_i_feel(!self.i_feel()),
+        }
+    }
+}
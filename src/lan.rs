@@ -0,0 +1,108 @@
+use crate::{FieldError, Message, Mode};
+
+/// Keys of Gree's WiFi LAN protocol that map to bits of the IR frame, in the order
+/// [`Message::to_lan`] lists them.
+pub const LAN_KEYS: [&str; 15] = [
+    "Pow",
+    "Mod",
+    "SetTem",
+    "TemUn",
+    "TemRec",
+    "WdSpd",
+    "SwUpDn",
+    "SwingLfRig",
+    "Lig",
+    "Tur",
+    "SwhSlp",
+    "Health",
+    "Blo",
+    "Air",
+    "SvSt",
+];
+
+impl Message {
+    /// The state as the values of Gree's WiFi LAN protocol, keyed by [`LAN_KEYS`].
+    ///
+    /// Most values are the IR bits as they are: `SetTem` is the setpoint in celsius
+    /// (the half degree for fahrenheit in `TemRec`), swing values match [`SwingMode`].
+    /// Only `WdSpd` differs, the app has 5 fan speeds where IR has 3.
+    ///
+    /// [`SwingMode`]: crate::SwingMode
+    pub fn to_lan(&self) -> [(&'static str, u8); 15] {
+        let s = &self.remote_state;
+        let bit = |byte: usize, i: u8| s[byte] >> i & 1;
+        let values = [
+            bit(0, 3),
+            s[0] & 0b111,
+            (s[1] & 0x0F) + 16,
+            bit(3, 3),
+            bit(3, 2),
+            match s[0] >> 4 & 0b11 {
+                0 => 0,
+                1 => 1,
+                2 => 3,
+                _ => 5,
+            },
+            s[4] & 0x0F,
+            s[4] >> 4,
+            bit(2, 5),
+            bit(2, 4),
+            bit(0, 7),
+            bit(2, 6),
+            bit(2, 7),
+            bit(3, 0),
+            bit(7, 2),
+        ];
+        let mut fields = [("", 0); 15];
+        for (field, (key, value)) in fields.iter_mut().zip(LAN_KEYS.into_iter().zip(values)) {
+            *field = (key, value);
+        }
+        fields
+    }
+
+    /// Sets one value of Gree's WiFi LAN protocol, see [`Message::to_lan`].
+    ///
+    /// Fan speeds 2 and 4 of the app round down to the nearest IR speed.
+    pub fn set_lan(&mut self, key: &str, value: u8) -> Result<(), FieldError> {
+        let invalid = u8::MAX;
+        let (byte, shift, width, value) = match key {
+            "Pow" => (0, 3, 1, value),
+            // Through the setter, changing the mode clears SE.
+            "Mod" => {
+                let mode = Mode::try_from(value).map_err(|_| FieldError::InvalidValue)?;
+                self.set_mode(mode);
+                return Ok(());
+            }
+            "SetTem" => (1, 0, 4, value.checked_sub(16).unwrap_or(invalid)),
+            "TemUn" => (3, 3, 1, value),
+            "TemRec" => (3, 2, 1, value),
+            "WdSpd" => {
+                let fan = match value {
+                    0 => 0,
+                    1 | 2 => 1,
+                    3 | 4 => 2,
+                    5 => 3,
+                    _ => invalid,
+                };
+                (0, 4, 2, fan)
+            }
+            "SwUpDn" => (4, 0, 4, value),
+            "SwingLfRig" => (4, 4, 4, value),
+            "Lig" => (2, 5, 1, value),
+            "Tur" => (2, 4, 1, value),
+            "SwhSlp" => (0, 7, 1, value),
+            "Health" => (2, 6, 1, value),
+            "Blo" => (2, 7, 1, value),
+            "Air" => (3, 0, 1, value),
+            "SvSt" => (7, 2, 1, value),
+            _ => return Err(FieldError::UnknownField),
+        };
+        if value >> width != 0 {
+            return Err(FieldError::InvalidValue);
+        }
+        let mask = ((1 << width) - 1) << shift;
+        self.remote_state[byte] = self.remote_state[byte] & !mask | value << shift;
+        self.update_checksum();
+        Ok(())
+    }
+}
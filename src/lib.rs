@@ -1,8 +1,86 @@
-#![no_std]
-
-use core::{fmt::Debug, hint::unreachable_unchecked, iter::once};
+//! IR encoding and decoding for Gree air conditioners.
+//!
+//! Most public enums are `#[non_exhaustive]`, as more protocol variants are supported
+//! they gain variants. Matches on them from other crates need a wildcard arm.
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#![no_std]
+#![forbid(unsafe_code)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "fmt")]
+use core::fmt::Debug;
+use core::time::Duration;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+mod bits;
+mod block;
+mod builder;
+#[cfg(feature = "encode")]
+mod cache;
+mod cached;
+#[cfg(feature = "clap")]
+mod clap_impl;
+mod convert;
+#[cfg(feature = "testing")]
+pub mod corrupt;
+#[cfg(feature = "fmt")]
+mod display;
+mod encode;
+#[cfg(all(feature = "alloc", feature = "fmt"))]
+mod explain;
+mod field;
+mod frame;
+mod key;
+mod lan;
+mod parse;
+#[cfg(feature = "decode")]
+mod partial;
+mod persist;
+pub mod presets;
+mod protocol;
+#[cfg(feature = "encode")]
+mod pulse;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "decode")]
+mod trace;
+pub mod typestate;
+mod validate;
+
+pub use bits::PAYLOAD_BITS;
+pub use block::{Block1, Block2};
+pub use builder::MessageBuilder;
+#[cfg(feature = "encode")]
+pub use cache::FrameCache;
+pub use cached::CachedMessage;
+#[cfg(feature = "encode")]
+pub use encode::EncodeIter;
+pub use encode::FRAME_LEN;
+#[cfg(all(feature = "alloc", feature = "fmt"))]
+pub use explain::{explain, Annotation};
+pub use field::{Change, Diff, Field, FieldError, FieldInfo, FieldValue, Fields};
+pub use frame::RawFrame;
+pub use key::Key;
+pub use lan::LAN_KEYS;
+#[cfg(feature = "decode")]
+pub use partial::Partial;
+pub use persist::{PERSIST_LEN, PERSIST_VERSION};
+pub use protocol::{IrCommand, IrProtocol};
+#[cfg(feature = "encode")]
+pub use pulse::PULSE_LEN;
+#[cfg(feature = "decode")]
+pub use trace::DecodeTrace;
+pub use validate::{Violation, Violations};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
 pub enum Code {
     Start,
     Continue,
@@ -12,6 +90,48 @@ pub enum Code {
     Long,  // 1
 }
 
+impl Code {
+    /// `S`, `C` and `E` for the markers, `0` and `1` for data.
+    pub fn to_char(&self) -> char {
+        match self {
+            Code::Start => 'S',
+            Code::Continue => 'C',
+            Code::End => 'E',
+            Code::Short => '0',
+            Code::Long => '1',
+        }
+    }
+
+    pub fn is_marker(&self) -> bool {
+        matches!(self, Code::Start | Code::Continue | Code::End)
+    }
+
+    pub fn is_data(&self) -> bool {
+        matches!(self, Code::Short | Code::Long)
+    }
+}
+
+impl TryFrom<char> for Code {
+    type Error = DecodeError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'S' => Ok(Code::Start),
+            'C' => Ok(Code::Continue),
+            'E' => Ok(Code::End),
+            '0' => Ok(Code::Short),
+            '1' => Ok(Code::Long),
+            _ => Err(DecodeError::InvalidCode),
+        }
+    }
+}
+
+impl From<Code> for char {
+    fn from(code: Code) -> char {
+        code.to_char()
+    }
+}
+
 impl From<bool> for Code {
     fn from(value: bool) -> Self {
         if value {
@@ -22,129 +142,353 @@ impl From<bool> for Code {
     }
 }
 
-impl TryInto<u8> for &Code {
+/// Fails for markers.
+impl TryFrom<Code> for bool {
     type Error = DecodeError;
 
-    fn try_into(self) -> Result<u8, Self::Error> {
-        match self {
+    fn try_from(code: Code) -> Result<bool, Self::Error> {
+        match code {
             Code::Start | Code::Continue | Code::End => Err(DecodeError::UnexpectedMarker),
-            Code::Short => Ok(0),
-            Code::Long => Ok(1),
+            Code::Short => Ok(false),
+            Code::Long => Ok(true),
         }
     }
 }
 
+/// Fails for markers.
+impl TryFrom<Code> for u8 {
+    type Error = DecodeError;
+
+    fn try_from(code: Code) -> Result<u8, Self::Error> {
+        bool::try_from(code).map(u8::from)
+    }
+}
+
+/// Deprecated, use `u8::try_from(code)` instead. Kept for compatibility.
+impl TryInto<u8> for &Code {
+    type Error = DecodeError;
+
+    fn try_into(self) -> Result<u8, Self::Error> {
+        u8::try_from(*self)
+    }
+}
+
+/// Deprecated, use `bool::try_from(code)` instead. Kept for compatibility.
 impl TryInto<bool> for &Code {
     type Error = DecodeError;
 
     fn try_into(self) -> Result<bool, Self::Error> {
-        match self {
-            Code::Start | Code::Continue | Code::End => Err(DecodeError::UnexpectedMarker),
-            Code::Short => Ok(false),
-            Code::Long => Ok(true),
-        }
+        bool::try_from(*self)
     }
 }
 
-#[derive(Clone)]
+/// The state of the AC as carried by one frame.
+///
+/// It is `Copy` and only 16 bytes large: the 8 bytes of the frame plus the [`Model`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Message {
     remote_state: [u8; 8],
+    model: Model,
 }
 
 impl Message {
-    pub fn new() -> Self {
+    /// The remote's factory settings: off, auto mode, 25 ℃, auto fan, every feature off.
+    pub const fn new() -> Self {
+        Self::with_model(Model::DEFAULT)
+    }
+
+    pub const fn with_model(model: Model) -> Self {
         let mut msg = Self {
-            remote_state: [0, 0, 0, 0b01010000, 0, 0b00100000, 0, 0],
+            remote_state: [0, 25 - 16, 0, 0b01010000, 0, 0b00100000, 0, 0],
+            model,
         };
         msg.update_checksum();
         msg
     }
 
-    pub fn raw(&self) -> &[u8; 8] {
+    /// Turned on, cooling to `temp` with automatic fan speed.
+    pub fn cool(temp: Temperature) -> Result<Self, TemperatureRangeError> {
+        Self::cool_with_model(Model::DEFAULT, temp)
+    }
+
+    /// Like [`Message::cool`], checking `temp` against `model` instead of
+    /// [`Model::DEFAULT`].
+    pub fn cool_with_model(model: Model, temp: Temperature) -> Result<Self, TemperatureRangeError> {
+        Self::running(model, Mode::Cold, temp)
+    }
+
+    /// Turned on, heating to `temp` with automatic fan speed.
+    pub fn heat(temp: Temperature) -> Result<Self, TemperatureRangeError> {
+        Self::heat_with_model(Model::DEFAULT, temp)
+    }
+
+    /// Like [`Message::heat`], checking `temp` against `model` instead of
+    /// [`Model::DEFAULT`].
+    pub fn heat_with_model(model: Model, temp: Temperature) -> Result<Self, TemperatureRangeError> {
+        Self::running(model, Mode::Hot, temp)
+    }
+
+    /// Turned on, only running the fan at automatic speed.
+    pub fn fan_only() -> Self {
+        Self::builder().on(true).mode(Mode::Wind).build()
+    }
+
+    fn running(model: Model, mode: Mode, temp: Temperature) -> Result<Self, TemperatureRangeError> {
+        if !model.supports_temperature(temp) {
+            return Err(TemperatureRangeError(temp.degrees()));
+        }
+        Ok(Self::builder()
+            .model(model)
+            .on(true)
+            .mode(mode)
+            .temperature(temp)
+            .build())
+    }
+
+    pub fn builder() -> MessageBuilder {
+        MessageBuilder::new()
+    }
+
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    pub fn set_model(&mut self, model: Model) {
+        self.model = model;
+    }
+
+    pub const fn raw(&self) -> &[u8; 8] {
         &self.remote_state
     }
 
-    pub fn encode(&self) -> impl Iterator<Item = Code> + '_ {
-        let byte_to_codes = |x| (0..8).map(move |i| Code::from(x >> i & 1u8 != 0u8));
-        let code1 = self.remote_state[..4].iter().flat_map(byte_to_codes);
-        let code2 = self.remote_state[4..].iter().flat_map(byte_to_codes);
-        once(Code::Start)
-            .chain(code1)
-            .chain(MAGIC_3.into_iter())
-            .chain(once(Code::Continue))
-            .chain(code2)
-            .chain(once(Code::End))
+    #[cfg(feature = "encode")]
+    pub fn encode(&self) -> EncodeIter {
+        EncodeIter::new(self.remote_state)
+    }
+
+    /// Like [`Message::encode`], but refuses to emit fields the AC would not understand.
+    #[cfg(feature = "encode")]
+    pub fn try_encode(&self) -> Result<EncodeIter, EncodeError> {
+        self.mode().map_err(|_| EncodeError::InvalidMode)?;
+        self.temperature()
+            .map_err(|_| EncodeError::InvalidTemperature)?;
+        self.timer().map_err(|_| EncodeError::InvalidTimerSetting)?;
+        if self.econo() && !matches!(self.mode(), Ok(Mode::Cold)) {
+            return Err(EncodeError::EconoOutsideCool);
+        }
+        if !self.model.supports_v_swing(self.v_swing())
+            || !self.model.supports_h_swing(self.h_swing())
+        {
+            return Err(EncodeError::UnsupportedSwing);
+        }
+        Ok(self.encode())
     }
 
+    /// Decodes a frame, keeping every bit of it (including the ones this crate doesn't
+    /// understand yet) so that encoding the result reproduces the capture exactly.
+    #[cfg(feature = "decode")]
     pub fn decode(codes: &[Code; 70]) -> Result<Self, DecodeError> {
-        let mut message = Self::new();
-        let mut iter = codes.iter();
-        // Start
-        let Code::Start = iter.next().ok_or(DecodeError::Eof)? else {
-            return Err(DecodeError::InvalidMarker);
+        Self::decode_iter(codes.iter().copied())
+    }
+
+    /// Decodes the frame at the start of `codes`, ignoring anything after it.
+    ///
+    /// Returns the message and the number of codes it took up.
+    #[cfg(feature = "decode")]
+    pub fn decode_slice(codes: &[Code]) -> Result<(Self, usize), DecodeError> {
+        let message = Self::decode_iter(codes.iter().copied())?;
+        Ok((message, FRAME_LEN))
+    }
+
+    /// Decodes a frame from a stream of codes, consuming exactly one frame's worth of
+    /// them on success.
+    #[cfg(feature = "decode")]
+    pub fn decode_iter(codes: impl IntoIterator<Item = Code>) -> Result<Self, DecodeError> {
+        Self::decode_traced(codes, &mut ())
+    }
+
+    /// Like [`Message::decode_iter`], but the error tells at which code decoding failed.
+    #[cfg(feature = "decode")]
+    pub fn decode_located(codes: impl IntoIterator<Item = Code>) -> Result<Self, LocatedError> {
+        let mut count: usize = 0;
+        // The magic is only checked once all of it has been read.
+        let mut magic = MAGIC_3;
+        let mut iter = codes.into_iter().inspect(|&code| {
+            if let Some(slot) = count.checked_sub(33).and_then(|i| magic.get_mut(i)) {
+                *slot = code;
+            }
+            count += 1;
+        });
+        let result = Self::decode_iter(&mut iter);
+        drop(iter);
+        result.map_err(|error| {
+            let index = match error {
+                // Reported once the whole block has been read.
+                DecodeError::Checksum { .. } => 65,
+                DecodeError::InvalidMagic => {
+                    33 + (0..3).find(|&i| magic[i] != MAGIC_3[i]).unwrap_or(0)
+                }
+                // The code that is missing.
+                DecodeError::Eof => count,
+                _ => count.saturating_sub(1),
+            };
+            LocatedError { index, error }
+        })
+    }
+
+    /// Like [`Message::decode_iter`], but a wrong magic or checksum is returned as a
+    /// warning next to the best-effort message instead of failing.
+    ///
+    /// Re-encoding the message always emits the standard magic.
+    #[cfg(feature = "decode")]
+    pub fn decode_lenient(
+        codes: impl IntoIterator<Item = Code>,
+    ) -> Result<(Self, Warnings), DecodeError> {
+        Self::decode_with(codes, &DecodeOptions::LENIENT)
+    }
+
+    /// Decodes with the checks chosen in `options`. Checks that are turned off report
+    /// their failures as warnings instead.
+    #[cfg(feature = "decode")]
+    pub fn decode_with(
+        codes: impl IntoIterator<Item = Code>,
+        options: &DecodeOptions,
+    ) -> Result<(Self, Warnings), DecodeError> {
+        let mut warnings = Warnings::default();
+        let message = Self::decode_inner(codes, &mut (), options, &mut warnings)?;
+        Ok((message, warnings))
+    }
+
+    /// Like [`Message::decode_iter`], reporting each part of the frame and then each
+    /// field to `trace` as it is decoded.
+    #[cfg(feature = "decode")]
+    pub fn decode_traced(
+        codes: impl IntoIterator<Item = Code>,
+        trace: &mut impl DecodeTrace,
+    ) -> Result<Self, DecodeError> {
+        Self::decode_inner(
+            codes,
+            trace,
+            &DecodeOptions::STRICT,
+            &mut Warnings::default(),
+        )
+    }
+
+    #[cfg(feature = "decode")]
+    fn decode_inner(
+        codes: impl IntoIterator<Item = Code>,
+        trace: &mut impl DecodeTrace,
+        options: &DecodeOptions,
+        warnings: &mut Warnings,
+    ) -> Result<Self, DecodeError> {
+        let mut check = |verify: bool, result: Result<(), DecodeError>| match result {
+            Err(error) if !verify => {
+                warnings.push(error);
+                Ok(())
+            }
+            result => result,
         };
+        let mut iter = codes.into_iter();
+        let mut message = Self {
+            remote_state: [0; 8],
+            model: options.model,
+        };
+        // Start
+        let result = check_marker(&mut iter, Code::Start);
+        trace.part("start", 0, &result);
+        result?;
         // Code 1
-        for v in message.remote_state[..4].iter_mut() {
-            for i in 0..8 {
-                let t: &Code = iter.next().ok_or(DecodeError::Eof)?;
-                *v |= TryInto::<u8>::try_into(t)? << i;
-            }
-        }
-        check_magic_code3(&mut iter)?;
+        let result = decode_block(&mut iter, &mut message.remote_state[..4]);
+        let block1 = u32::from_le_bytes([
+            message.remote_state[0],
+            message.remote_state[1],
+            message.remote_state[2],
+            message.remote_state[3],
+        ]);
+        trace.part("block1", block1, &result);
+        result?;
+        let result = check_magic_code3(&mut iter);
+        trace.part("magic", 0, &result);
+        check(options.verify_magic, result)?;
         // Continue
-        let Code::Continue = iter.next().ok_or(DecodeError::Eof)? else {
-            return Err(DecodeError::InvalidMarker);
-        };
+        let result = check_marker(&mut iter, Code::Continue);
+        trace.part("continue", 0, &result);
+        result?;
         // Code 2
-        for v in message.remote_state[4..].iter_mut() {
-            for i in 0..8 {
-                let t: &Code = iter.next().ok_or(DecodeError::Eof)?;
-                *v |= TryInto::<u8>::try_into(t)? << i;
-            }
-        }
+        let result = decode_block(&mut iter, &mut message.remote_state[4..]);
+        let block2 = u32::from_le_bytes([
+            message.remote_state[4],
+            message.remote_state[5],
+            message.remote_state[6],
+            message.remote_state[7],
+        ]);
+        trace.part("block2", block2, &result);
+        result?;
         // End
-        let Code::End = iter.next().ok_or(DecodeError::Eof)? else {
-            return Err(DecodeError::InvalidMarker);
-        };
+        let result = check_marker(&mut iter, Code::End);
+        trace.part("end", 0, &result);
+        result?;
         // Checksum
-        if message.checksum() != message.remote_state[7] >> 4 {
-            return Err(DecodeError::Checksum);
+        let result = message.check_checksum();
+        trace.part("checksum", (message.remote_state[7] >> 4) as u32, &result);
+        check(options.verify_checksum, result)?;
+        if options.strict_ranges {
+            message.mode()?;
+            message.temperature()?;
+            message.timer()?;
+        }
+        if options.strict_swing && !(message.v_swing().is_known() && message.h_swing().is_known()) {
+            return Err(DecodeError::InvalidSwingMode);
+        }
+        for info in message.fields() {
+            trace.field(&info);
         }
         Ok(message)
     }
 
-    fn checksum(&self) -> u8 {
-        let mut sum = 10;
-        // Sum the lower half of the first 4 bytes of this block.
-        for v in self.remote_state.iter().take(4) {
-            sum += *v & 0xF;
-        }
-        // then sum the upper half of the next 3 bytes.
-        for v in self.remote_state[4..].iter().take(3) {
-            sum += *v >> 4;
+    /// Checks the structure and checksum of a capture without keeping the result.
+    #[cfg(feature = "decode")]
+    pub fn verify(codes: &[Code; 70]) -> Result<(), DecodeError> {
+        Self::decode(codes).map(|_| ())
+    }
+
+    /// The checksum of the current state, the AC expects it in the upper nibble of the
+    /// last byte.
+    pub const fn checksum(&self) -> u8 {
+        checksum_block(&self.remote_state)
+    }
+
+    #[cfg(any(feature = "decode", feature = "serde"))]
+    fn check_checksum(&self) -> Result<(), DecodeError> {
+        let expected = self.checksum();
+        let found = self.remote_state[7] >> 4;
+        if expected != found {
+            let mut block = [0; 7];
+            block.copy_from_slice(&self.remote_state[..7]);
+            return Err(DecodeError::Checksum {
+                expected,
+                found,
+                block,
+            });
         }
-        // Trim it down to fit into the 4 bits allowed. i.e. Mod 16.
-        sum & 0xF
+        Ok(())
     }
 
-    fn update_checksum(&mut self) {
+    const fn update_checksum(&mut self) {
         self.remote_state[7] &= 0x0F;
         self.remote_state[7] |= self.checksum() << 4;
     }
 
     pub fn mode(&self) -> Result<Mode, DecodeError> {
-        match self.remote_state[0] & 0b111 {
-            0 => Ok(Mode::Auto),
-            1 => Ok(Mode::Cold),
-            2 => Ok(Mode::Dry),
-            3 => Ok(Mode::Wind),
-            4 => Ok(Mode::Hot),
-            _ => Err(DecodeError::InvalidMode),
-        }
+        Mode::decode(self.remote_state[0])
     }
 
-    pub fn set_mode(&mut self, mode: Mode) {
-        self.remote_state[0] = self.remote_state[0] & 0b1111_1000 | mode as u8;
+    pub const fn set_mode(&mut self, mode: Mode) {
+        self.remote_state[0] = self.remote_state[0] & 0b1111_1000 | mode.encode();
+        if !matches!(mode, Mode::Cold) {
+            // SE is a cooling-only function, the remote drops it on mode change.
+            self.remote_state[7] &= 0b1111_1101;
+        }
         self.update_checksum();
     }
 
@@ -152,31 +496,53 @@ impl Message {
         self.remote_state[0] >> 3 & 1 != 0
     }
 
-    pub fn set_on(&mut self, on: bool) {
+    pub const fn set_on(&mut self, on: bool) {
         self.remote_state[0] = self.remote_state[0] & 0b1111_0111 | (on as u8) << 3;
         self.update_checksum();
     }
 
+    pub fn toggle_power(&mut self) {
+        self.set_on(!self.is_on());
+    }
+
+    /// Switches to the next mode in the order the remote's MODE key cycles through.
+    pub fn cycle_mode(&mut self) {
+        self.set_mode(match self.mode() {
+            Ok(Mode::Auto) => Mode::Cold,
+            Ok(Mode::Cold) => Mode::Dry,
+            Ok(Mode::Dry) => Mode::Wind,
+            Ok(Mode::Wind) => Mode::Hot,
+            Ok(Mode::Hot) | Err(_) => Mode::Auto,
+        });
+        // The unit picks the fan speed in dry mode, the remote shows it as locked.
+        let fan = self.effective_fan();
+        self.set_fan(fan);
+    }
+
     pub fn fan(&self) -> Fan {
-        match self.remote_state[0] >> 4 & 0b11 {
-            0 => Fan::Auto,
-            1 => Fan::Level1,
-            2 => Fan::Level2,
-            3 => Fan::Level3,
-            _ => unsafe { unreachable_unchecked() },
-        }
+        Fan::decode(self.remote_state[0] >> 4)
     }
 
-    pub fn set_fan(&mut self, fan: Fan) {
-        self.remote_state[0] = self.remote_state[0] & 0b1100_1111 | (fan as u8) << 4;
+    pub const fn set_fan(&mut self, fan: Fan) {
+        self.remote_state[0] = self.remote_state[0] & 0b1100_1111 | fan.encode() << 4;
         self.update_checksum();
     }
 
+    /// Switches to the next fan speed, wrapping from level 3 back to auto.
+    pub fn cycle_fan(&mut self) {
+        self.set_fan(match self.fan() {
+            Fan::Auto => Fan::Level1,
+            Fan::Level1 => Fan::Level2,
+            Fan::Level2 => Fan::Level3,
+            Fan::Level3 => Fan::Auto,
+        });
+    }
+
     pub fn swing(&self) -> bool {
         self.remote_state[0] >> 6 & 1 != 0
     }
 
-    pub fn set_swing(&mut self, swing: bool) {
+    pub const fn set_swing(&mut self, swing: bool) {
         self.remote_state[0] = self.remote_state[0] & 0b1011_1111 | (swing as u8) << 6;
         self.update_checksum();
     }
@@ -185,46 +551,79 @@ impl Message {
         self.remote_state[0] >> 7 & 1 != 0
     }
 
-    pub fn set_sleep(&mut self, sleep: bool) {
+    pub const fn set_sleep(&mut self, sleep: bool) {
         self.remote_state[0] = self.remote_state[0] & 0b0111_1111 | (sleep as u8) << 7;
         self.update_checksum();
     }
 
     pub fn temperature(&self) -> Result<Temperature, DecodeError> {
-        // TODO: support fahrenheit
-        let value = self.remote_state[1] & 0x0F;
-        if value <= 30 - 16 {
-            Ok(Temperature::Centigrade(value + 16))
+        let temp = Temperature::decode(
+            self.remote_state[1],
+            self.remote_state[3] >> 2 & 1 != 0,
+            self.remote_state[3] >> 3 & 1 != 0,
+        );
+        if self.model.supports_temperature(temp) {
+            Ok(temp)
         } else {
             Err(DecodeError::InvalidTemperature)
         }
     }
 
-    pub fn set_temperature(&mut self, temp: Temperature) {
-        let value = match temp {
-            Temperature::Centigrade(degree) if degree >= 16 && degree <= 30 => degree - 16,
-            _ => 25 - 16,
-        };
-        self.remote_state[1] = self.remote_state[1] & 0xF0 | value;
+    /// Sets the temperature, falling back to 25 ℃ if it is out of the model's range.
+    pub const fn set_temperature(&mut self, temp: Temperature) {
+        [self.remote_state[1], self.remote_state[3]] = self.temperature_bytes(temp);
         self.update_checksum();
     }
 
+    /// Bytes 1 and 3 of the state with `temp` written into them, for
+    /// [`Message::set_temperature`] and [`CachedMessage::set_temperature`].
+    const fn temperature_bytes(&self, temp: Temperature) -> [u8; 2] {
+        let (value, extra, fahrenheit) = match temp.encode() {
+            Ok(encoded) if self.model.supports_temperature(temp) => encoded,
+            // 25 ℃, what the remote starts at.
+            _ => (25 - 16, false, false),
+        };
+        [
+            self.remote_state[1] & 0xF0 | value,
+            self.remote_state[3] & 0b1111_0011 | (extra as u8) << 2 | (fahrenheit as u8) << 3,
+        ]
+    }
+
+    /// Raises the temperature by one degree, stopping at the model's maximum.
+    pub fn temperature_up(&mut self) {
+        let temp = match self.temperature() {
+            Ok(temp) => temp.step_up(&self.model),
+            Err(_) => Temperature::Centigrade(25),
+        };
+        self.set_temperature(temp);
+    }
+
+    /// Lowers the temperature by one degree, stopping at the model's minimum.
+    pub fn temperature_down(&mut self) {
+        let temp = match self.temperature() {
+            Ok(temp) => temp.step_down(&self.model),
+            Err(_) => Temperature::Centigrade(25),
+        };
+        self.set_temperature(temp);
+    }
+
     pub fn timer(&self) -> Result<TimerSetting, DecodeError> {
-        TimerSetting::try_from(self.remote_state[1] >> 4 | self.remote_state[2] << 4)
+        TimerSetting::decode(self.remote_state[1] >> 4 | self.remote_state[2] << 4)
     }
 
-    pub fn set_timer(&mut self, setting: &TimerSetting) {
-        let value: u8 = setting.into();
+    pub fn set_timer(&mut self, setting: &TimerSetting) -> Result<(), EncodeError> {
+        let value = setting.encode()?;
         self.remote_state[1] = self.remote_state[1] & 0x0F | value << 4;
-        self.remote_state[2] = self.remote_state[2] & 0xF0 | value & 0x0F;
+        self.remote_state[2] = self.remote_state[2] & 0xF0 | value >> 4;
         self.update_checksum();
+        Ok(())
     }
 
     pub fn turbo(&self) -> bool {
         self.remote_state[2] >> 4 & 1 != 0
     }
 
-    pub fn set_turbo(&mut self, turbo: bool) {
+    pub const fn set_turbo(&mut self, turbo: bool) {
         self.remote_state[2] = self.remote_state[2] & 0b1110_1111 | (turbo as u8) << 4;
         self.update_checksum();
     }
@@ -233,16 +632,26 @@ impl Message {
         self.remote_state[2] >> 5 & 1 != 0
     }
 
-    pub fn set_light(&mut self, light: bool) {
+    pub const fn set_light(&mut self, light: bool) {
         self.remote_state[2] = self.remote_state[2] & 0b1101_1111 | (light as u8) << 5;
         self.update_checksum();
     }
 
+    /// Returns the frame the remote sends when the LIGHT key is pressed in state `current`.
+    ///
+    /// Only the light bit is flipped, every other setting is kept, so it can be used
+    /// to turn the display on or off without touching the rest of the AC state.
+    pub fn light_toggle_from(current: &Message) -> Message {
+        let mut msg = *current;
+        msg.set_light(!current.light());
+        msg
+    }
+
     pub fn health(&self) -> bool {
         self.remote_state[2] >> 6 & 1 != 0
     }
 
-    pub fn set_health(&mut self, health: bool) {
+    pub const fn set_health(&mut self, health: bool) {
         self.remote_state[2] = self.remote_state[2] & 0b1011_1111 | (health as u8) << 6;
         self.update_checksum();
     }
@@ -251,7 +660,7 @@ impl Message {
         self.remote_state[2] >> 7 & 1 != 0
     }
 
-    pub fn set_dry(&mut self, dry: bool) {
+    pub const fn set_dry(&mut self, dry: bool) {
         self.remote_state[2] = self.remote_state[2] & 0b0111_1111 | (dry as u8) << 7;
         self.update_checksum();
     }
@@ -260,77 +669,35 @@ impl Message {
         self.remote_state[3] & 1 != 0
     }
 
-    pub fn set_ventilateo(&mut self, ventilate: bool) {
+    pub const fn set_ventilateo(&mut self, ventilate: bool) {
         self.remote_state[3] = self.remote_state[3] & 0b1111_1110 | ventilate as u8;
         self.update_checksum();
     }
 
     pub fn v_swing(&self) -> SwingMode {
-        match self.remote_state[4] & 0xF {
-            0 => SwingMode::Off,
-            1 => SwingMode::On,
-            2 => SwingMode::Unknown2,
-            3 => SwingMode::Unknown3,
-            4 => SwingMode::Unknown4,
-            5 => SwingMode::Unknown5,
-            6 => SwingMode::Unknown6,
-            7 => SwingMode::Unknown7,
-            8 => SwingMode::Unknown8,
-            9 => SwingMode::Unknown9,
-            10 => SwingMode::Unknown10,
-            11 => SwingMode::Unknown11,
-            12 => SwingMode::Unknown12,
-            13 => SwingMode::Unknown13,
-            14 => SwingMode::Unknown14,
-            15 => SwingMode::Unknown15,
-            _ => unsafe { unreachable_unchecked() },
-        }
-    }
-
-    pub fn set_v_swing(&mut self, mode: SwingMode) {
-        self.remote_state[4] = self.remote_state[4] & 0xF0 | mode as u8;
+        SwingMode::decode(self.remote_state[4])
+    }
+
+    pub const fn set_v_swing(&mut self, mode: SwingMode) {
+        self.remote_state[4] = self.remote_state[4] & 0xF0 | mode.encode();
         self.update_checksum();
     }
 
     pub fn h_swing(&self) -> SwingMode {
-        match self.remote_state[4] >> 4 {
-            0 => SwingMode::Off,
-            1 => SwingMode::On,
-            2 => SwingMode::Unknown2,
-            3 => SwingMode::Unknown3,
-            4 => SwingMode::Unknown4,
-            5 => SwingMode::Unknown5,
-            6 => SwingMode::Unknown6,
-            7 => SwingMode::Unknown7,
-            8 => SwingMode::Unknown8,
-            9 => SwingMode::Unknown9,
-            10 => SwingMode::Unknown10,
-            11 => SwingMode::Unknown11,
-            12 => SwingMode::Unknown12,
-            13 => SwingMode::Unknown13,
-            14 => SwingMode::Unknown14,
-            15 => SwingMode::Unknown15,
-            _ => unsafe { unreachable_unchecked() },
-        }
-    }
-
-    pub fn set_h_swing(&mut self, mode: SwingMode) {
-        self.remote_state[4] = self.remote_state[4] & 0x0F | (mode as u8) << 4;
+        SwingMode::decode(self.remote_state[4] >> 4)
+    }
+
+    pub const fn set_h_swing(&mut self, mode: SwingMode) {
+        self.remote_state[4] = self.remote_state[4] & 0x0F | mode.encode() << 4;
         self.update_checksum();
     }
 
     pub fn temperature_display(&self) -> TemperatureDisplay {
-        match self.remote_state[5] & 0b11 {
-            0 => TemperatureDisplay::Setting,
-            1 => TemperatureDisplay::Room,
-            2 => TemperatureDisplay::Indoor,
-            3 => TemperatureDisplay::Outdoor,
-            _ => unsafe { unreachable_unchecked() },
-        }
+        TemperatureDisplay::decode(self.remote_state[5])
     }
 
-    pub fn set_temperature_display(&mut self, temp_display: TemperatureDisplay) {
-        self.remote_state[5] = self.remote_state[5] & 0b1111_1100 | temp_display as u8;
+    pub const fn set_temperature_display(&mut self, temp_display: TemperatureDisplay) {
+        self.remote_state[5] = self.remote_state[5] & 0b1111_1100 | temp_display.encode();
         self.update_checksum();
     }
 
@@ -338,7 +705,7 @@ impl Message {
         self.remote_state[5] >> 2 & 1 != 0
     }
 
-    pub fn set_i_feel(&mut self, i_feel: bool) {
+    pub const fn set_i_feel(&mut self, i_feel: bool) {
         self.remote_state[5] = self.remote_state[5] & 0b1111_1011 | (i_feel as u8) << 2;
         self.update_checksum();
     }
@@ -347,21 +714,60 @@ impl Message {
         self.remote_state[5] >> 6 & 1 != 0
     }
 
-    pub fn set_wifi(&mut self, wifi: bool) {
+    pub const fn set_wifi(&mut self, wifi: bool) {
         self.remote_state[5] = self.remote_state[5] & 0b1011_1111 | (wifi as u8) << 6;
         self.update_checksum();
     }
 
+    /// ID of the paired indoor unit, for layouts that address one of several units.
+    pub fn unit_id(&self) -> Option<UnitId> {
+        if self.remote_state[6] >> 7 & 1 != 0 {
+            Some(UnitId(self.remote_state[6] & 0x0F))
+        } else {
+            None
+        }
+    }
+
+    /// Targets the unit with the given ID, or all units for `None`.
+    pub const fn set_unit_id(&mut self, unit_id: Option<UnitId>) {
+        self.remote_state[6] = match unit_id {
+            Some(UnitId(id)) => self.remote_state[6] & 0b0111_0000 | 1 << 7 | id,
+            None => self.remote_state[6] & 0b0111_0000,
+        };
+        self.update_checksum();
+    }
+
     pub fn econo(&self) -> bool {
         self.remote_state[7] >> 2 & 1 != 0
     }
 
-    pub fn set_econo(&mut self, econo: bool) {
+    pub const fn set_econo(&mut self, econo: bool) {
         self.remote_state[7] = self.remote_state[7] & 0b1111_1011 | (econo as u8) << 2;
         self.update_checksum();
     }
+
+    /// The SE energy-saving mode found on newer remotes, separate from [`Message::econo`].
+    pub fn se(&self) -> bool {
+        self.remote_state[7] >> 1 & 1 != 0
+    }
+
+    /// Enables or disables SE mode.
+    ///
+    /// SE is only available while cooling, enabling it in any other mode has no effect.
+    pub const fn set_se(&mut self, se: bool) {
+        let se = se && self.remote_state[0] & 0b111 == Mode::Cold as u8;
+        self.remote_state[7] = self.remote_state[7] & 0b1111_1101 | (se as u8) << 1;
+        self.update_checksum();
+    }
+}
+
+impl Default for Message {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
+#[cfg(feature = "fmt")]
 impl Debug for Message {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Message")
@@ -382,35 +788,213 @@ impl Debug for Message {
             .field("temperature_display", &self.temperature_display())
             .field("i_feel", &self.i_feel())
             .field("wifi", &self.wifi())
+            .field("unit_id", &self.unit_id())
             .field("econo", &self.econo())
+            .field("se", &self.se())
             .finish()
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[non_exhaustive]
 pub enum DecodeError {
     InvalidMarker,
     UnexpectedMarker,
+    InvalidCode,
     InvalidMode,
     InvalidTimerSetting,
+    InvalidTimerStep,
     InvalidFan,
     InvalidTemperature,
     InvalidSwingMode,
+    InvalidTemperatureDisplay,
+    InvalidUnitId,
     InvalidMagic,
+    /// A persisted record has a layout version this crate doesn't know.
+    InvalidVersion,
     Eof,
-    Checksum,
+    /// The checksum sent doesn't match the one computed over `block`, the 7 bytes it covers.
+    Checksum {
+        expected: u8,
+        found: u8,
+        block: [u8; 7],
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for DecodeError {}
+
+/// The checks [`Message::decode_with`] runs.
+#[cfg(feature = "decode")]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[non_exhaustive]
+pub struct DecodeOptions {
+    pub verify_checksum: bool,
+    pub verify_magic: bool,
+    /// Reject frames whose mode, temperature or timer are out of range for `model`.
+    pub strict_ranges: bool,
+    /// Reject frames with a swing value not seen in captures, see [`SwingMode::is_known`].
+    pub strict_swing: bool,
+    /// The model set on decoded messages, used for range checks.
+    pub model: Model,
+}
+
+#[cfg(feature = "decode")]
+impl DecodeOptions {
+    /// What [`Message::decode`] does: verify the magic and checksum, leave ranges to the
+    /// getters.
+    pub const STRICT: DecodeOptions = DecodeOptions {
+        verify_checksum: true,
+        verify_magic: true,
+        strict_ranges: false,
+        strict_swing: false,
+        model: Model::DEFAULT,
+    };
+
+    /// What [`Message::decode_lenient`] does: only require a well-formed frame.
+    pub const LENIENT: DecodeOptions = DecodeOptions {
+        verify_checksum: false,
+        verify_magic: false,
+        strict_ranges: false,
+        strict_swing: false,
+        model: Model::DEFAULT,
+    };
+}
+
+#[cfg(feature = "decode")]
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self::STRICT
+    }
+}
+
+/// Errors that [`DecodeOptions`] told decoding to tolerate, in the order they were found.
+#[cfg(feature = "decode")]
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct Warnings {
+    errors: [Option<DecodeError>; 2],
+}
+
+#[cfg(feature = "decode")]
+impl Warnings {
+    pub fn is_empty(&self) -> bool {
+        self.errors[0].is_none()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &DecodeError> {
+        self.errors.iter().flatten()
+    }
+
+    fn push(&mut self, error: DecodeError) {
+        if let Some(slot) = self.errors.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(error);
+        }
+    }
+}
+
+/// A [`DecodeError`] together with the index of the code it was found at.
+#[cfg(feature = "decode")]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct LocatedError {
+    pub index: usize,
+    pub error: DecodeError,
+}
+
+#[cfg(feature = "decode")]
+#[cfg(feature = "fmt")]
+impl core::error::Error for LocatedError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[non_exhaustive]
+pub enum EncodeError {
+    InvalidMode,
+    InvalidTimerSetting,
+    InvalidTemperature,
+    EconoOutsideCool,
+    UnsupportedSwing,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[cfg(feature = "fmt")]
+impl core::error::Error for EncodeError {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
 pub enum Mode {
     Auto,
+    #[cfg_attr(feature = "serde", serde(rename = "cool"))]
     Cold,
     Dry,
+    #[cfg_attr(feature = "serde", serde(rename = "fan"))]
     Wind,
+    #[cfg_attr(feature = "serde", serde(rename = "heat"))]
     Hot,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// The field codecs below work on the raw bits as they appear in the frame, so custom
+/// layouts can reuse them. Decoders ignore bits above the field's width.
+impl Mode {
+    /// The 3-bit value sent for the mode.
+    pub const fn encode(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn decode(bits: u8) -> Result<Mode, DecodeError> {
+        Self::BY_BITS[(bits & 0b111) as usize].ok_or(DecodeError::InvalidMode)
+    }
+
+    const BY_BITS: [Option<Mode>; 8] = [
+        Some(Mode::Auto),
+        Some(Mode::Cold),
+        Some(Mode::Dry),
+        Some(Mode::Wind),
+        Some(Mode::Hot),
+        None,
+        None,
+        None,
+    ];
+
+    /// Whether the setpoint can be changed, it is fixed in auto mode and unused in fan mode.
+    pub fn supports_temperature(&self) -> bool {
+        matches!(self, Mode::Cold | Mode::Dry | Mode::Hot)
+    }
+
+    /// The fan speeds the unit honours, it picks a low speed by itself when drying.
+    pub fn allowed_fan_speeds(&self) -> &'static [Fan] {
+        match self {
+            Mode::Dry => &[Fan::Level1],
+            _ => &[Fan::Auto, Fan::Level1, Fan::Level2, Fan::Level3],
+        }
+    }
+
+    /// The setpoint the remote shows when switching to this mode, `None` in fan mode.
+    pub fn default_temperature(&self) -> Option<Temperature> {
+        match self {
+            Mode::Auto | Mode::Cold | Mode::Dry => Some(Temperature::Centigrade(25)),
+            Mode::Hot => Some(Temperature::Centigrade(28)),
+            Mode::Wind => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
 pub enum Fan {
     Auto,
     Level1,
@@ -418,23 +1002,507 @@ pub enum Fan {
     Level3,
 }
 
-#[derive(Clone, Copy)]
+impl Fan {
+    /// The 2-bit value sent for the fan speed.
+    pub const fn encode(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn decode(bits: u8) -> Fan {
+        Self::BY_BITS[(bits & 0b11) as usize]
+    }
+
+    const BY_BITS: [Fan; 4] = [Fan::Auto, Fan::Level1, Fan::Level2, Fan::Level3];
+
+    /// Fan speed as a percentage, `None` for [`Fan::Auto`].
+    pub fn to_percent(&self) -> Option<u8> {
+        match self {
+            Fan::Auto => None,
+            Fan::Level1 => Some(33),
+            Fan::Level2 => Some(66),
+            Fan::Level3 => Some(100),
+        }
+    }
+
+    /// Picks the fan level whose [percentage](Fan::to_percent) is closest, ties going to
+    /// the faster one. `0` selects [`Fan::Auto`].
+    ///
+    /// Values above 100 are treated as 100.
+    pub fn from_percent(percent: u8) -> Fan {
+        match percent {
+            0 => Fan::Auto,
+            1..=49 => Fan::Level1,
+            50..=82 => Fan::Level2,
+            _ => Fan::Level3,
+        }
+    }
+}
+
+/// Capabilities that differ between AC models.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Model {
+    pub min_celsius: u8,
+    pub max_celsius: u8,
+    pub min_fahrenheit: u8,
+    pub max_fahrenheit: u8,
+    /// The [`SwingMode`]s the vertical louver supports, one bit per value.
+    pub v_swing: u16,
+    /// The [`SwingMode`]s the horizontal louver supports, 0 if there is none.
+    pub h_swing: u16,
+}
+
+impl Model {
+    /// 16–30 ℃ or 61–86 ℉, accepted by most remotes.
+    pub const DEFAULT: Model = Model {
+        min_celsius: 16,
+        max_celsius: 30,
+        min_fahrenheit: 61,
+        max_fahrenheit: 86,
+        v_swing: Model::ALL_SWING,
+        h_swing: Model::ALL_SWING,
+    };
+
+    /// 16–31 ℃ or 61–88 ℉, the full range the protocol can carry.
+    pub const EXTENDED: Model = Model {
+        min_celsius: 16,
+        max_celsius: 31,
+        min_fahrenheit: 61,
+        max_fahrenheit: 88,
+        v_swing: Model::ALL_SWING,
+        h_swing: Model::ALL_SWING,
+    };
+
+    /// Allows every swing value in [`Model::v_swing`] or [`Model::h_swing`].
+    pub const ALL_SWING: u16 = u16::MAX;
+
+    pub const fn supports_temperature(&self, temp: Temperature) -> bool {
+        match temp {
+            Temperature::Centigrade(degree) => {
+                // The protocol can't carry anything outside of 16–31 ℃.
+                degree >= 16
+                    && degree <= 31
+                    && degree >= self.min_celsius
+                    && degree <= self.max_celsius
+            }
+            Temperature::Fahrenheit(degree) => {
+                degree >= 61
+                    && degree <= 88
+                    && degree >= self.min_fahrenheit
+                    && degree <= self.max_fahrenheit
+            }
+        }
+    }
+
+    pub fn supports_v_swing(&self, mode: SwingMode) -> bool {
+        self.v_swing >> mode as u8 & 1 != 0
+    }
+
+    pub fn supports_h_swing(&self, mode: SwingMode) -> bool {
+        self.h_swing >> mode as u8 & 1 != 0
+    }
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A temperature setpoint.
+///
+/// Equality is structural: `Centigrade(24)` and `Fahrenheit(75)` are different values,
+/// just as they are different frames on the wire.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Temperature {
     Centigrade(u8),
+    Fahrenheit(u8),
+}
+
+impl Temperature {
+    /// Lowest setpoint of [`Model::DEFAULT`].
+    pub const MIN: Temperature = Temperature::Centigrade(16);
+    /// Highest setpoint of [`Model::DEFAULT`].
+    pub const MAX: Temperature = Temperature::Centigrade(30);
+
+    /// The number of degrees, in whichever unit the temperature is in.
+    pub fn degrees(&self) -> u8 {
+        match *self {
+            Temperature::Centigrade(degree) | Temperature::Fahrenheit(degree) => degree,
+        }
+    }
+
+    /// One degree warmer, like the remote's up key it stops at the model's maximum.
+    ///
+    /// The result is always within the model's range and the protocol's, even if `self`
+    /// was not.
+    pub fn step_up(&self, model: &Model) -> Temperature {
+        self.step(model, |degree| degree.saturating_add(1))
+    }
+
+    /// One degree cooler, like the remote's down key it stops at the model's minimum.
+    ///
+    /// The result is always within the model's range and the protocol's, even if `self`
+    /// was not.
+    pub fn step_down(&self, model: &Model) -> Temperature {
+        self.step(model, |degree| degree.saturating_sub(1))
+    }
+
+    fn step(&self, model: &Model, step: impl FnOnce(u8) -> u8) -> Temperature {
+        // Bounded by the protocol's range too, whatever the model claims.
+        match *self {
+            Temperature::Centigrade(degree) => Temperature::Centigrade(
+                step(degree)
+                    .max(model.min_celsius.max(16))
+                    .min(model.max_celsius.min(31)),
+            ),
+            Temperature::Fahrenheit(degree) => Temperature::Fahrenheit(
+                step(degree)
+                    .max(model.min_fahrenheit.max(61))
+                    .min(model.max_fahrenheit.min(88)),
+            ),
+        }
+    }
+
+    /// The setpoint in degrees celsius, converting from fahrenheit if needed.
+    pub fn as_celsius_f32(&self) -> f32 {
+        match *self {
+            Temperature::Centigrade(degree) => degree as f32,
+            Temperature::Fahrenheit(degree) => (degree as f32 - 32.0) * 5.0 / 9.0,
+        }
+    }
+
+    /// The setpoint in degrees fahrenheit, converting from celsius if needed.
+    pub fn as_fahrenheit_f32(&self) -> f32 {
+        match *self {
+            Temperature::Centigrade(degree) => degree as f32 * 9.0 / 5.0 + 32.0,
+            Temperature::Fahrenheit(degree) => degree as f32,
+        }
+    }
+
+    /// Rounds to the nearest whole degree centigrade, halves rounding up.
+    ///
+    /// Only the range the protocol can carry is checked.
+    pub fn from_celsius_f32(degree: f32) -> Result<Self, FloatTemperatureError> {
+        // Checked before rounding, which saturates negative values and NaN to 0.
+        if !(15.5..31.5).contains(&degree) {
+            return Err(FloatTemperatureError(degree));
+        }
+        Ok(Temperature::Centigrade(round_half_up(degree)))
+    }
+
+    /// Rounds to the nearest whole degree fahrenheit, halves rounding up.
+    ///
+    /// Only the range the protocol can carry is checked.
+    pub fn from_fahrenheit_f32(degree: f32) -> Result<Self, FloatTemperatureError> {
+        if !(60.5..88.5).contains(&degree) {
+            return Err(FloatTemperatureError(degree));
+        }
+        Ok(Temperature::Fahrenheit(round_half_up(degree)))
+    }
+
+    /// The 4-bit setpoint, the extra half degree bit and the fahrenheit flag sent for the
+    /// temperature.
+    ///
+    /// Fails outside of the protocol's range of 16–31 ℃ and 61–88 ℉, whatever the model.
+    pub const fn encode(&self) -> Result<(u8, bool, bool), EncodeError> {
+        if !Model::EXTENDED.supports_temperature(*self) {
+            return Err(EncodeError::InvalidTemperature);
+        }
+        // Written with matches instead of `?` and `Option` combinators to stay `const`.
+        let encoded = match *self {
+            Temperature::Centigrade(degree) => match degree.checked_sub(16) {
+                Some(value) => Some((value, false, false)),
+                None => None,
+            },
+            Temperature::Fahrenheit(degree) => match degree.checked_sub(32) {
+                Some(above) => {
+                    // Half degrees celsius, the extra bit carries the odd half.
+                    let half_degrees = (above as u16 * 10 + 4) / 9;
+                    match (half_degrees / 2).checked_sub(16) {
+                        Some(value) => Some((value as u8, half_degrees & 1 != 0, true)),
+                        None => None,
+                    }
+                }
+                None => None,
+            },
+        };
+        match encoded {
+            Some(encoded) => Ok(encoded),
+            None => Err(EncodeError::InvalidTemperature),
+        }
+    }
+
+    /// The inverse of [`Temperature::encode`], without checking any model's range.
+    pub fn decode(bits: u8, extra: bool, fahrenheit: bool) -> Temperature {
+        let value = bits & 0x0F;
+        if fahrenheit {
+            Temperature::Fahrenheit(Self::FAHRENHEIT[(value << 1 | extra as u8) as usize])
+        } else {
+            Temperature::Centigrade(value + 16)
+        }
+    }
+
+    /// Degrees fahrenheit by the nibble and the extra bit, which together count half
+    /// degrees celsius from 16 ℃.
+    const FAHRENHEIT: [u8; 32] = {
+        let mut table = [0; 32];
+        let mut i = 0;
+        while i < table.len() {
+            let half_degrees = ((i as u16 >> 1) + 16) * 2 + (i as u16 & 1);
+            table[i] = ((half_degrees * 9 + 5) / 10 + 32) as u8;
+            i += 1;
+        }
+        table
+    };
+
+    pub fn centigrade(degree: u8, model: &Model) -> Result<Self, DecodeError> {
+        let temp = Temperature::Centigrade(degree);
+        if model.supports_temperature(temp) {
+            Ok(temp)
+        } else {
+            Err(DecodeError::InvalidTemperature)
+        }
+    }
+
+    pub fn fahrenheit(degree: u8, model: &Model) -> Result<Self, DecodeError> {
+        let temp = Temperature::Fahrenheit(degree);
+        if model.supports_temperature(temp) {
+            Ok(temp)
+        } else {
+            Err(DecodeError::InvalidTemperature)
+        }
+    }
+}
+
+/// Orders by physical temperature, so mixed units compare as expected.
+///
+/// Equal temperatures in different units (25 ℃ and 77 ℉) order centigrade first,
+/// keeping the order consistent with `Eq`.
+impl Ord for Temperature {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        // Fifths of a degree fahrenheit are exact for both units.
+        let key = |temp: &Temperature| match *temp {
+            Temperature::Centigrade(degree) => (degree as u16 * 9 + 160, 0),
+            Temperature::Fahrenheit(degree) => (degree as u16 * 5, 1),
+        };
+        key(self).cmp(&key(other))
+    }
 }
 
+impl PartialOrd for Temperature {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Converts degrees centigrade, checked against [`Model::DEFAULT`].
+impl TryFrom<u8> for Temperature {
+    type Error = TemperatureRangeError;
+
+    fn try_from(degree: u8) -> Result<Self, Self::Error> {
+        Temperature::centigrade(degree, &Model::DEFAULT).map_err(|_| TemperatureRangeError(degree))
+    }
+}
+
+/// A setpoint that is out of range, carrying the rejected number of degrees.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct TemperatureRangeError(pub u8);
+
+impl From<TemperatureRangeError> for DecodeError {
+    fn from(_: TemperatureRangeError) -> Self {
+        DecodeError::InvalidTemperature
+    }
+}
+
+/// A setpoint in fractional degrees that is out of range or not a number, carrying the
+/// rejected value.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct FloatTemperatureError(pub f32);
+
+impl From<FloatTemperatureError> for DecodeError {
+    fn from(_: FloatTemperatureError) -> Self {
+        DecodeError::InvalidTemperature
+    }
+}
+
+#[cfg(feature = "fmt")]
 impl Debug for Temperature {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Temperature::Centigrade(degree) => f.write_fmt(format_args!("{} ℃", degree)),
+            Temperature::Fahrenheit(degree) => f.write_fmt(format_args!("{} ℉", degree)),
+        }
+    }
+}
+
+/// The ID of one unit in a multi-unit installation, at most [`UnitId::MAX`] as it is sent
+/// in 4 bits.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct UnitId(u8);
+
+impl UnitId {
+    pub const MAX: u8 = 15;
+
+    /// `None` for IDs above [`UnitId::MAX`]. It is `const`, for
+    /// [`gree_frame!`](crate::gree_frame).
+    pub const fn new(id: u8) -> Option<Self> {
+        if id <= Self::MAX {
+            Some(Self(id))
+        } else {
+            None
         }
     }
+
+    pub const fn get(self) -> u8 {
+        self.0
+    }
 }
 
-#[derive(Clone, Copy, Debug)]
+impl TryFrom<u8> for UnitId {
+    type Error = DecodeError;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        Self::new(id).ok_or(DecodeError::InvalidUnitId)
+    }
+}
+
+impl From<UnitId> for u8 {
+    fn from(id: UnitId) -> u8 {
+        id.0
+    }
+}
+
+/// A timer setting, always one a frame can carry.
+///
+/// [`TimerSetting::new`] and [`TimerSetting::from_half_hours`] only build the steps the
+/// remote can set. Decoding (and deserializing) also accepts the other values a frame
+/// can carry, such as 10.5 hours or up to 29.5 hours.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
 pub struct TimerSetting {
-    pub enabled: bool,
-    pub half_hours: u8,
+    enabled: bool,
+    half_hours: u8,
+}
+
+impl TimerSetting {
+    /// Longest timer the remote can set, in half hours.
+    pub const MAX_HALF_HOURS: u8 = 48;
+
+    /// No timer.
+    pub const OFF: TimerSetting = TimerSetting {
+        enabled: false,
+        half_hours: 0,
+    };
+
+    /// Creates an enabled timer running for `hours` and `minutes`.
+    ///
+    /// `minutes` must be 0 or 30, and beyond 10 hours only whole hours are allowed.
+    pub fn new(hours: u8, minutes: u8) -> Result<Self, DecodeError> {
+        let half = match minutes {
+            0 => 0,
+            30 => 1,
+            _ => return Err(DecodeError::InvalidTimerStep),
+        };
+        if hours > Self::MAX_HALF_HOURS / 2 {
+            return Err(DecodeError::InvalidTimerStep);
+        }
+        Self::from_half_hours(true, hours * 2 + half)
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn half_hours(&self) -> u8 {
+        self.half_hours
+    }
+
+    pub fn with_enabled(self, enabled: bool) -> Self {
+        Self { enabled, ..self }
+    }
+
+    /// Creates a timer the way the remote allows it: 30-minute steps up to 10 hours,
+    /// whole hours beyond that, and at most 24 hours.
+    pub fn from_half_hours(enabled: bool, half_hours: u8) -> Result<Self, DecodeError> {
+        if half_hours > Self::MAX_HALF_HOURS || half_hours > 20 && half_hours & 1 != 0 {
+            return Err(DecodeError::InvalidTimerStep);
+        }
+        Ok(Self {
+            enabled,
+            half_hours,
+        })
+    }
+
+    /// Like [`TimerSetting::from_half_hours`], but accepts every timer a frame can carry,
+    /// as [`TimerSetting::decode`] does, so decoded captures can be stored and restored.
+    pub(crate) fn from_frame_half_hours(
+        enabled: bool,
+        half_hours: u8,
+    ) -> Result<Self, DecodeError> {
+        let setting = Self {
+            enabled,
+            half_hours,
+        };
+        setting
+            .encode()
+            .map_err(|_| DecodeError::InvalidTimerSetting)?;
+        Ok(setting)
+    }
+
+    /// The closest timer the remote can set: longer ones are capped at
+    /// [`MAX_HALF_HOURS`](Self::MAX_HALF_HOURS), and odd half hours beyond 10 hours are
+    /// rounded up to the next hour.
+    pub(crate) fn rounded(enabled: bool, half_hours: u8) -> Self {
+        let half_hours = match half_hours {
+            h if h > Self::MAX_HALF_HOURS => Self::MAX_HALF_HOURS,
+            h => h + (h > 20 && h & 1 != 0) as u8,
+        };
+        Self {
+            enabled,
+            half_hours,
+        }
+    }
+
+    /// Creates an enabled timer, rounding to the nearest step the remote can set.
+    ///
+    /// Fails for durations over 24 hours.
+    pub fn try_from_duration(duration: Duration) -> Result<Self, DecodeError> {
+        const HALF_HOUR: u64 = 30 * 60;
+        if duration > Duration::from_secs(Self::MAX_HALF_HOURS as u64 * HALF_HOUR) {
+            return Err(DecodeError::InvalidTimerSetting);
+        }
+        // Round to the nearest half hour, halves rounding up.
+        let secs = duration.as_secs() + (duration.subsec_nanos() >= 500_000_000) as u64;
+        let mut half_hours = ((secs + HALF_HOUR / 2) / HALF_HOUR) as u8;
+        if half_hours > 20 {
+            // Beyond 10 hours only whole hours are allowed.
+            half_hours = ((secs + HALF_HOUR) / (2 * HALF_HOUR) * 2) as u8;
+        }
+        Self::from_half_hours(true, half_hours)
+    }
+
+    pub fn to_duration(&self) -> Duration {
+        Duration::from_secs(self.half_hours as u64 * 30 * 60)
+    }
+
+    /// The 8-bit value sent for the timer, see the [`TryFrom`] impl for `u8`.
+    pub fn encode(&self) -> Result<u8, EncodeError> {
+        u8::try_from(self)
+    }
+
+    /// The inverse of [`TimerSetting::encode`].
+    pub fn decode(bits: u8) -> Result<Self, DecodeError> {
+        Self::try_from(bits)
+    }
 }
 
 impl TryFrom<u8> for TimerSetting {
@@ -456,17 +1524,28 @@ impl TryFrom<u8> for TimerSetting {
     }
 }
 
-impl Into<u8> for &TimerSetting {
-    fn into(self) -> u8 {
-        let hours = self.half_hours / 2;
-        let half = self.half_hours % 2;
+/// Fails for timers over 29.5 hours, which don't fit into the two decimal digits sent.
+impl TryFrom<&TimerSetting> for u8 {
+    type Error = EncodeError;
+
+    fn try_from(setting: &TimerSetting) -> Result<u8, Self::Error> {
+        let hours = setting.half_hours / 2;
+        let half = setting.half_hours % 2;
         let tens = hours / 10;
         let units = hours % 10;
-        half | tens << 1 | (self.enabled as u8) << 3 | units << 4
+        if tens > 2 {
+            return Err(EncodeError::InvalidTimerSetting);
+        }
+        Ok(half | tens << 1 | (setting.enabled as u8) << 3 | units << 4)
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
 pub enum SwingMode {
     Off,
     On,
@@ -486,7 +1565,68 @@ pub enum SwingMode {
     Unknown15,
 }
 
-#[derive(Clone, Copy, Debug)]
+/// The values known from captures are: 0 off, 1 full swing, 2–6 fixed positions from
+/// top to bottom, and 7, 9 and 11 swinging over the lower, middle and upper range.
+impl SwingMode {
+    /// The 4-bit value sent for the louver setting.
+    pub const fn encode(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn decode(bits: u8) -> SwingMode {
+        Self::BY_BITS[(bits & 0xF) as usize]
+    }
+
+    const BY_BITS: [SwingMode; 16] = [
+        SwingMode::Off,
+        SwingMode::On,
+        SwingMode::Unknown2,
+        SwingMode::Unknown3,
+        SwingMode::Unknown4,
+        SwingMode::Unknown5,
+        SwingMode::Unknown6,
+        SwingMode::Unknown7,
+        SwingMode::Unknown8,
+        SwingMode::Unknown9,
+        SwingMode::Unknown10,
+        SwingMode::Unknown11,
+        SwingMode::Unknown12,
+        SwingMode::Unknown13,
+        SwingMode::Unknown14,
+        SwingMode::Unknown15,
+    ];
+
+    pub fn is_swinging(&self) -> bool {
+        matches!(
+            self,
+            SwingMode::On | SwingMode::Unknown7 | SwingMode::Unknown9 | SwingMode::Unknown11
+        )
+    }
+
+    pub fn is_fixed(&self) -> bool {
+        self.fixed_position().is_some()
+    }
+
+    /// Whether the value has been seen in captures, see above.
+    pub fn is_known(&self) -> bool {
+        *self == SwingMode::Off || self.is_swinging() || self.is_fixed()
+    }
+
+    /// The fixed louver position, 1 (top) to 5 (bottom).
+    pub fn fixed_position(&self) -> Option<u8> {
+        match *self as u8 {
+            value @ 2..=6 => Some(value - 1),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
 pub enum TemperatureDisplay {
     Setting,
     Room,
@@ -494,15 +1634,196 @@ pub enum TemperatureDisplay {
     Outdoor,
 }
 
+impl TemperatureDisplay {
+    /// The 2-bit value sent for the display setting.
+    pub const fn encode(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn decode(bits: u8) -> TemperatureDisplay {
+        Self::BY_BITS[(bits & 0b11) as usize]
+    }
+
+    const BY_BITS: [TemperatureDisplay; 4] = [
+        TemperatureDisplay::Setting,
+        TemperatureDisplay::Room,
+        TemperatureDisplay::Indoor,
+        TemperatureDisplay::Outdoor,
+    ];
+}
+
+/// The Gree nibble checksum over the state bytes, as shown by [`Message::raw`].
+///
+/// Only the first 7 bytes are covered, missing ones count as zero, so captures from
+/// other tools can be checked whether or not they include the checksum byte.
+pub const fn checksum_block(bytes: &[u8]) -> u8 {
+    let mut sum = 10;
+    let mut i = 0;
+    while i < bytes.len() && i < 7 {
+        // Sum the lower half of the first 4 bytes of this block, then the upper half of
+        // the next 3 bytes.
+        sum += if i < 4 { bytes[i] & 0xF } else { bytes[i] >> 4 };
+        i += 1;
+    }
+    // Trim it down to fit into the 4 bits allowed. i.e. Mod 16.
+    sum & 0xF
+}
+
+/// Whether `codes` starts with something shaped like a Gree frame: long enough, with the
+/// markers and the magic in place. Trailing codes are ignored, as in
+/// [`Message::decode_slice`].
+///
+/// Only a few codes are compared, so it is cheap enough to sort out other protocols in an
+/// interrupt handler before a full [`Message::decode_slice`].
+pub fn is_gree_frame(codes: &[Code]) -> bool {
+    codes.len() >= encode::FRAME_LEN
+        && codes[0] == Code::Start
+        && codes[33..36] == MAGIC_3
+        && codes[36] == Code::Continue
+        && codes[69] == Code::End
+}
+
+/// The checksum of a frame packed by [`Message::to_bytes`].
+pub fn checksum_frame(bytes: &[u8; 9]) -> u8 {
+    checksum_block(&bytes[..8])
+}
+
+/// `f32::round` lives in std, this saturates to `0..=255` and maps NaN to 0.
+fn round_half_up(value: f32) -> u8 {
+    (value + 0.5) as u8
+}
+
 const MAGIC_3: [Code; 3] = [Code::Short, Code::Long, Code::Short];
 
-fn check_magic_code3<'a>(iter: &mut impl Iterator<Item = &'a Code>) -> Result<(), DecodeError> {
+#[cfg(feature = "decode")]
+fn check_marker(iter: &mut impl Iterator<Item = Code>, expected: Code) -> Result<(), DecodeError> {
+    match iter.next() {
+        Some(code) if code == expected => Ok(()),
+        Some(_) => Err(DecodeError::InvalidMarker),
+        None => Err(DecodeError::Eof),
+    }
+}
+
+#[cfg(feature = "decode")]
+fn decode_block(
+    iter: &mut impl Iterator<Item = Code>,
+    block: &mut [u8],
+) -> Result<(), DecodeError> {
+    for v in block.iter_mut() {
+        for i in 0..8 {
+            let t: Code = iter.next().ok_or(DecodeError::Eof)?;
+            *v |= u8::try_from(t)? << i;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "decode")]
+fn check_magic_code3(iter: &mut impl Iterator<Item = Code>) -> Result<(), DecodeError> {
     let mut codes = [Code::Short; 3];
     for v in codes.iter_mut() {
-        *v = *iter.next().ok_or(DecodeError::Eof)?;
+        *v = iter.next().ok_or(DecodeError::Eof)?;
     }
     match codes {
         MAGIC_3 => Ok(()),
         _ => Err(DecodeError::InvalidMagic),
     }
 }
+
+// The assertions need `Debug`.
+#[cfg(all(test, feature = "fmt"))]
+mod tests {
+    use crate::{Message, Model, Temperature, TimerSetting, UnitId};
+
+    #[test]
+    fn temperature_steps_stay_in_the_protocol_range() {
+        let model = Model {
+            max_celsius: 35,
+            min_fahrenheit: 50,
+            ..Model::EXTENDED
+        };
+        let mut msg = Message::with_model(model);
+        msg.set_temperature(Temperature::Centigrade(31));
+        msg.temperature_up();
+        assert_eq!(msg.temperature(), Ok(Temperature::Centigrade(31)));
+        assert_eq!(
+            Temperature::Fahrenheit(61).step_down(&model),
+            Temperature::Fahrenheit(61)
+        );
+    }
+
+    #[test]
+    fn unit_ids_must_fit_into_4_bits() {
+        assert_eq!(UnitId::new(16), None);
+        assert_eq!(UnitId::try_from(17), Err(crate::DecodeError::InvalidUnitId));
+        let mut msg = Message::new();
+        msg.set_unit_id(UnitId::new(15));
+        assert_eq!(msg.unit_id().map(UnitId::get), Some(15));
+    }
+
+    #[test]
+    fn float_temperatures_keep_the_rejected_value() {
+        use crate::FloatTemperatureError;
+        assert_eq!(
+            Temperature::from_celsius_f32(-40.0),
+            Err(FloatTemperatureError(-40.0))
+        );
+        assert!(Temperature::from_fahrenheit_f32(f32::NAN).is_err_and(|e| e.0.is_nan()));
+        assert_eq!(
+            Temperature::from_celsius_f32(31.4),
+            Ok(Temperature::Centigrade(31))
+        );
+        assert_eq!(
+            Temperature::from_fahrenheit_f32(60.5),
+            Ok(Temperature::Fahrenheit(61))
+        );
+        assert!(Temperature::from_fahrenheit_f32(88.5).is_err());
+    }
+
+    #[test]
+    fn cool_checks_the_given_model() {
+        use crate::TemperatureRangeError;
+        let temp = Temperature::Centigrade(31);
+        assert_eq!(Message::cool(temp), Err(TemperatureRangeError(31)));
+        let msg = Message::cool_with_model(Model::EXTENDED, temp).unwrap();
+        assert_eq!(msg.temperature(), Ok(temp));
+        assert_eq!(msg.model(), &Model::EXTENDED);
+    }
+
+    #[test]
+    fn fan_from_percent_picks_the_closest_level() {
+        use crate::Fan;
+        for fan in [Fan::Level1, Fan::Level2, Fan::Level3] {
+            assert_eq!(Fan::from_percent(fan.to_percent().unwrap()), fan);
+        }
+        assert_eq!(Fan::from_percent(49), Fan::Level1);
+        assert_eq!(Fan::from_percent(50), Fan::Level2);
+        assert_eq!(Fan::from_percent(83), Fan::Level3);
+    }
+
+    #[test]
+    fn set_timer_round_trips() {
+        for half_hours in 0..=TimerSetting::MAX_HALF_HOURS {
+            for enabled in [false, true] {
+                let Ok(timer) = TimerSetting::from_half_hours(enabled, half_hours) else {
+                    continue;
+                };
+                let mut msg = Message::new();
+                msg.set_turbo(true);
+                msg.set_timer(&timer).unwrap();
+                assert_eq!(msg.timer(), Ok(timer));
+                assert!(msg.turbo());
+            }
+        }
+    }
+
+    #[cfg(all(feature = "encode", feature = "decode"))]
+    #[test]
+    fn is_gree_frame_allows_trailing_codes() {
+        let mut codes = [crate::Code::Short; crate::FRAME_LEN + 3];
+        codes[..crate::FRAME_LEN].copy_from_slice(&Message::new().encode_frame());
+        assert!(crate::is_gree_frame(&codes));
+        assert!(Message::decode_slice(&codes).is_ok());
+        assert!(!crate::is_gree_frame(&codes[..crate::FRAME_LEN - 1]));
+    }
+}
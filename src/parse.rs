@@ -0,0 +1,115 @@
+use core::str::FromStr;
+
+use crate::{DecodeError, Fan, Mode, Model, SwingMode, Temperature, TemperatureDisplay};
+
+/// Finds the value whose names match `s`, ignoring ASCII case.
+fn lookup<T: Copy>(s: &str, table: &[(&[&str], T)]) -> Option<T> {
+    table
+        .iter()
+        .find(|(names, _)| names.iter().any(|name| name.eq_ignore_ascii_case(s)))
+        .map(|(_, value)| *value)
+}
+
+/// The names each value parses from, the first one is the canonical name.
+pub(crate) const MODE_NAMES: [(&[&str], Mode); 5] = [
+    (&["auto"], Mode::Auto),
+    (&["cool", "cold"], Mode::Cold),
+    (&["dry"], Mode::Dry),
+    (&["fan", "wind"], Mode::Wind),
+    (&["heat", "hot"], Mode::Hot),
+];
+
+pub(crate) const FAN_NAMES: [(&[&str], Fan); 4] = [
+    (&["auto"], Fan::Auto),
+    (&["level1", "1", "low"], Fan::Level1),
+    (&["level2", "2", "medium"], Fan::Level2),
+    (&["level3", "3", "high"], Fan::Level3),
+];
+
+pub(crate) const SWING_MODE_NAMES: [(&[&str], SwingMode); 16] = [
+    (&["off", "0"], SwingMode::Off),
+    (&["on", "1"], SwingMode::On),
+    (&["unknown2", "2"], SwingMode::Unknown2),
+    (&["unknown3", "3"], SwingMode::Unknown3),
+    (&["unknown4", "4"], SwingMode::Unknown4),
+    (&["unknown5", "5"], SwingMode::Unknown5),
+    (&["unknown6", "6"], SwingMode::Unknown6),
+    (&["unknown7", "7"], SwingMode::Unknown7),
+    (&["unknown8", "8"], SwingMode::Unknown8),
+    (&["unknown9", "9"], SwingMode::Unknown9),
+    (&["unknown10", "10"], SwingMode::Unknown10),
+    (&["unknown11", "11"], SwingMode::Unknown11),
+    (&["unknown12", "12"], SwingMode::Unknown12),
+    (&["unknown13", "13"], SwingMode::Unknown13),
+    (&["unknown14", "14"], SwingMode::Unknown14),
+    (&["unknown15", "15"], SwingMode::Unknown15),
+];
+
+pub(crate) const TEMPERATURE_DISPLAY_NAMES: [(&[&str], TemperatureDisplay); 4] = [
+    (&["setting"], TemperatureDisplay::Setting),
+    (&["room"], TemperatureDisplay::Room),
+    (&["indoor"], TemperatureDisplay::Indoor),
+    (&["outdoor"], TemperatureDisplay::Outdoor),
+];
+
+impl FromStr for Mode {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lookup(s, &MODE_NAMES).ok_or(DecodeError::InvalidMode)
+    }
+}
+
+impl FromStr for Fan {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lookup(s, &FAN_NAMES).ok_or(DecodeError::InvalidFan)
+    }
+}
+
+impl FromStr for SwingMode {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lookup(s, &SWING_MODE_NAMES).ok_or(DecodeError::InvalidSwingMode)
+    }
+}
+
+impl FromStr for TemperatureDisplay {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lookup(s, &TEMPERATURE_DISPLAY_NAMES).ok_or(DecodeError::InvalidTemperatureDisplay)
+    }
+}
+
+/// Parses `"24"`, `"24C"`, `"24°C"`, `"24℃"` as centigrade and `"75F"`, `"75°F"`, `"75℉"`
+/// as fahrenheit.
+///
+/// Only the range the protocol can carry is checked, use [`Model::supports_temperature`]
+/// for a specific model.
+impl FromStr for Temperature {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (number, unit) = s.split_at(digits);
+        let degree = number
+            .parse()
+            .map_err(|_| DecodeError::InvalidTemperature)?;
+        let unit = unit.trim_start();
+        let unit = unit.strip_prefix('°').unwrap_or(unit);
+        let temp = match unit {
+            "" | "C" | "c" | "℃" => Temperature::Centigrade(degree),
+            "F" | "f" | "℉" => Temperature::Fahrenheit(degree),
+            _ => return Err(DecodeError::InvalidTemperature),
+        };
+        if Model::EXTENDED.supports_temperature(temp) {
+            Ok(temp)
+        } else {
+            Err(DecodeError::InvalidTemperature)
+        }
+    }
+}
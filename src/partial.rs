@@ -0,0 +1,79 @@
+use crate::{check_magic_code3, check_marker, Code, DecodeError, Field, Message, Model};
+
+/// What [`Message::decode_partial`] recovered from a frame.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct Partial {
+    /// The state read so far, bits that were not reached are 0.
+    pub message: Message,
+    /// How many state bits were read, counted from the start of the frame.
+    pub bits: u8,
+    /// The first problem found, `None` if the frame decoded cleanly.
+    pub error: Option<DecodeError>,
+}
+
+impl Partial {
+    /// Whether every bit of `field` was read.
+    pub fn is_recovered(&self, field: Field) -> bool {
+        field.bits().iter().all(|range| range.end <= self.bits)
+    }
+
+    /// The recovered fields, in frame order.
+    pub fn recovered(&self) -> impl Iterator<Item = Field> + '_ {
+        Field::ALL
+            .into_iter()
+            .filter(|&field| self.is_recovered(field))
+    }
+}
+
+impl Message {
+    /// Decodes as much of a truncated or corrupted frame as possible.
+    ///
+    /// Decoding stops at the first missing or misplaced code. A wrong magic or checksum is
+    /// reported in [`Partial::error`] but keeps the bits, so fields recovered from such a
+    /// frame are unverified.
+    pub fn decode_partial(codes: impl IntoIterator<Item = Code>) -> Partial {
+        let mut iter = codes.into_iter();
+        let mut partial = Partial {
+            message: Message {
+                remote_state: [0; 8],
+                model: Model::DEFAULT,
+            },
+            bits: 0,
+            error: None,
+        };
+        let result = (|| {
+            check_marker(&mut iter, Code::Start)?;
+            read_bits(&mut iter, &mut partial, 32)?;
+            if let Err(error) = check_magic_code3(&mut iter) {
+                if error == DecodeError::Eof {
+                    return Err(error);
+                }
+                partial.error.get_or_insert(error);
+            }
+            check_marker(&mut iter, Code::Continue)?;
+            read_bits(&mut iter, &mut partial, 64)?;
+            check_marker(&mut iter, Code::End)?;
+            partial.message.check_checksum()
+        })();
+        if let Err(error) = result {
+            partial.error.get_or_insert(error);
+        }
+        partial
+    }
+}
+
+fn read_bits(
+    iter: &mut impl Iterator<Item = Code>,
+    partial: &mut Partial,
+    end: u8,
+) -> Result<(), DecodeError> {
+    while partial.bits < end {
+        let code = iter.next().ok_or(DecodeError::Eof)?;
+        let bit = u8::try_from(code)?;
+        let i = partial.bits as usize;
+        partial.message.remote_state[i / 8] |= bit << (i % 8);
+        partial.bits += 1;
+    }
+    Ok(())
+}
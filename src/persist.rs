@@ -0,0 +1,159 @@
+use crate::{
+    DecodeError, Fan, Message, Mode, Model, SwingMode, TemperatureDisplay, TimerSetting, UnitId,
+};
+
+/// Size of [`Message::to_persist_bytes`].
+pub const PERSIST_LEN: usize = 19;
+
+/// Layout version written by [`Message::to_persist_bytes`].
+pub const PERSIST_VERSION: u8 = 1;
+
+impl Message {
+    /// Packs the state into a fixed-size record for EEPROM or flash.
+    ///
+    /// Unlike [`Message::raw`] the layout doesn't follow the IR frame, so it stays the
+    /// same if the crate learns about more bits, and it includes the model. Bits outside
+    /// of the known fields are not kept. Version 1 is:
+    ///
+    /// | byte  | content                                                          |
+    /// |-------|------------------------------------------------------------------|
+    /// | 0     | [`PERSIST_VERSION`]                                              |
+    /// | 1     | mode, as in the frame                                            |
+    /// | 2     | fan, as in the frame                                             |
+    /// | 3     | setpoint, as in the frame                                        |
+    /// | 4     | bit 0 fahrenheit, bit 1 timer enabled, bit 2 extra half degree   |
+    /// | 5     | timer in half hours                                              |
+    /// | 6     | flags: on, swing, sleep, turbo, light, health, dry, ventilate    |
+    /// | 7     | flags: i_feel, wifi, econo, se                                   |
+    /// | 8     | vertical swing in the low nibble, horizontal in the high one     |
+    /// | 9     | temperature display, as in the frame                             |
+    /// | 10    | unit ID, `0xFF` for none                                         |
+    /// | 11–14 | model: min and max celsius, min and max fahrenheit               |
+    /// | 15–18 | model: vertical and horizontal swing masks, little endian        |
+    ///
+    /// An invalid mode or timer is stored as auto or off. The setpoint is stored as sent,
+    /// so it comes back even if it is outside of the model's range.
+    pub fn to_persist_bytes(&self) -> [u8; PERSIST_LEN] {
+        let timer = self.timer().unwrap_or(TimerSetting::OFF);
+        let flags = |bits: &[bool]| {
+            bits.iter()
+                .enumerate()
+                .fold(0, |acc, (i, &bit)| acc | (bit as u8) << i)
+        };
+        let mut bytes = [0; PERSIST_LEN];
+        bytes[0] = PERSIST_VERSION;
+        bytes[1] = self.mode().map_or(0, |mode| mode.encode());
+        bytes[2] = self.fan().encode();
+        bytes[3] = self.remote_state[1] & 0x0F;
+        bytes[4] = flags(&[
+            self.remote_state[3] >> 3 & 1 != 0,
+            timer.enabled(),
+            self.remote_state[3] >> 2 & 1 != 0,
+        ]);
+        bytes[5] = timer.half_hours();
+        bytes[6] = flags(&[
+            self.is_on(),
+            self.swing(),
+            self.sleep(),
+            self.turbo(),
+            self.light(),
+            self.health(),
+            self.dry(),
+            self.ventilate(),
+        ]);
+        bytes[7] = flags(&[self.i_feel(), self.wifi(), self.econo(), self.se()]);
+        bytes[8] = self.v_swing().encode() | self.h_swing().encode() << 4;
+        bytes[9] = self.temperature_display().encode();
+        bytes[10] = self.unit_id().map_or(0xFF, UnitId::get);
+        bytes[11] = self.model.min_celsius;
+        bytes[12] = self.model.max_celsius;
+        bytes[13] = self.model.min_fahrenheit;
+        bytes[14] = self.model.max_fahrenheit;
+        bytes[15..17].copy_from_slice(&self.model.v_swing.to_le_bytes());
+        bytes[17..19].copy_from_slice(&self.model.h_swing.to_le_bytes());
+        bytes
+    }
+
+    /// Restores a record written by [`Message::to_persist_bytes`].
+    ///
+    /// Fails with [`DecodeError::InvalidVersion`] for other layouts, including erased
+    /// flash, and for values a frame can't carry.
+    pub fn from_persist_bytes(bytes: &[u8; PERSIST_LEN]) -> Result<Self, DecodeError> {
+        if bytes[0] != PERSIST_VERSION {
+            return Err(DecodeError::InvalidVersion);
+        }
+        let flag = |byte: u8, i: u8| byte >> i & 1 != 0;
+        let model = Model {
+            min_celsius: bytes[11],
+            max_celsius: bytes[12],
+            min_fahrenheit: bytes[13],
+            max_fahrenheit: bytes[14],
+            v_swing: u16::from_le_bytes([bytes[15], bytes[16]]),
+            h_swing: u16::from_le_bytes([bytes[17], bytes[18]]),
+        };
+        // The timer is read back with the rule it was written with, any decodable one.
+        let timer = TimerSetting::from_frame_half_hours(flag(bytes[4], 1), bytes[5])?;
+        let mut message = Message::builder()
+            .model(model)
+            .mode(Mode::try_from(bytes[1])?)
+            .fan(Fan::try_from(bytes[2])?)
+            .timer(timer)
+            .on(flag(bytes[6], 0))
+            .swing(flag(bytes[6], 1))
+            .sleep(flag(bytes[6], 2))
+            .turbo(flag(bytes[6], 3))
+            .light(flag(bytes[6], 4))
+            .health(flag(bytes[6], 5))
+            .dry(flag(bytes[6], 6))
+            .ventilate(flag(bytes[6], 7))
+            .i_feel(flag(bytes[7], 0))
+            .wifi(flag(bytes[7], 1))
+            .econo(flag(bytes[7], 2))
+            .se(flag(bytes[7], 3))
+            .v_swing(SwingMode::decode(bytes[8]))
+            .h_swing(SwingMode::decode(bytes[8] >> 4))
+            .temperature_display(TemperatureDisplay::try_from(bytes[9])?)
+            .unit_id(match bytes[10] {
+                0xFF => None,
+                id => Some(UnitId::try_from(id)?),
+            })
+            .build();
+        // Written directly, the setpoint may be outside of the model's range.
+        message.remote_state[1] = message.remote_state[1] & 0xF0 | bytes[3] & 0x0F;
+        message.remote_state[3] = message.remote_state[3] & 0b1111_0011
+            | (flag(bytes[4], 2) as u8) << 2
+            | (flag(bytes[4], 0) as u8) << 3;
+        message.update_checksum();
+        Ok(message)
+    }
+}
+
+// The assertions need `Debug`.
+#[cfg(all(test, feature = "fmt"))]
+mod tests {
+    use crate::{presets, Message, Model, Temperature, TimerSetting};
+
+    #[test]
+    fn persist_round_trip() {
+        let mut msg = presets::eco_night();
+        msg.set_timer(&TimerSetting::new(12, 0).unwrap()).unwrap();
+        msg.set_temperature(Temperature::Fahrenheit(75));
+        assert_eq!(
+            Message::from_persist_bytes(&msg.to_persist_bytes()),
+            Ok(msg)
+        );
+    }
+
+    #[test]
+    fn persist_keeps_a_setpoint_outside_of_the_model() {
+        // What decoding a 31 ℃ frame gives, decoding always uses the default model.
+        let mut msg = Message::with_model(Model::EXTENDED);
+        msg.set_temperature(Temperature::Centigrade(31));
+        msg.set_model(Model::DEFAULT);
+        assert!(msg.temperature().is_err());
+        assert_eq!(
+            Message::from_persist_bytes(&msg.to_persist_bytes()),
+            Ok(msg)
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Ready-made [`Message`] values for common AC states.
+
+use crate::{Fan, Message, Mode, Temperature};
+
+/// Cooling at 24 ℃ with automatic fan speed.
+///
+/// ```
+/// let state = [0x09, 0x08, 0x00, 0x50, 0x00, 0x20, 0x00, 0xD0];
+/// assert_eq!(gree_ir::presets::cool().raw(), &state);
+/// ```
+pub fn cool() -> Message {
+    let mut msg = Message::new();
+    msg.set_on(true);
+    msg.set_mode(Mode::Cold);
+    msg.set_fan(Fan::Auto);
+    msg.set_temperature(Temperature::Centigrade(24));
+    msg
+}
+
+/// Heating at 22 ℃ with automatic fan speed.
+///
+/// ```
+/// let state = [0x0C, 0x06, 0x00, 0x50, 0x00, 0x20, 0x00, 0xE0];
+/// assert_eq!(gree_ir::presets::heat().raw(), &state);
+/// ```
+pub fn heat() -> Message {
+    let mut msg = Message::new();
+    msg.set_on(true);
+    msg.set_mode(Mode::Hot);
+    msg.set_fan(Fan::Auto);
+    msg.set_temperature(Temperature::Centigrade(22));
+    msg
+}
+
+/// Dehumidifying at 25 ℃ with the lowest fan speed.
+///
+/// ```
+/// let state = [0x1A, 0x09, 0x00, 0x50, 0x00, 0x20, 0x00, 0xF0];
+/// assert_eq!(gree_ir::presets::dehumidify().raw(), &state);
+/// ```
+pub fn dehumidify() -> Message {
+    let mut msg = Message::new();
+    msg.set_on(true);
+    msg.set_mode(Mode::Dry);
+    msg.set_fan(Fan::Level1);
+    msg.set_temperature(Temperature::Centigrade(25));
+    msg
+}
+
+/// Fan only, no cooling or heating.
+///
+/// ```
+/// let state = [0x0B, 0x09, 0x00, 0x50, 0x00, 0x20, 0x00, 0x00];
+/// assert_eq!(gree_ir::presets::fan_only().raw(), &state);
+/// ```
+pub fn fan_only() -> Message {
+    let mut msg = Message::new();
+    msg.set_on(true);
+    msg.set_mode(Mode::Wind);
+    msg.set_fan(Fan::Auto);
+    msg
+}
+
+/// Quiet cooling at 26 ℃ for the night, with sleep and econo enabled and the light off.
+///
+/// ```
+/// let state = [0x99, 0x0A, 0x00, 0x50, 0x00, 0x20, 0x00, 0xF4];
+/// assert_eq!(gree_ir::presets::eco_night().raw(), &state);
+/// ```
+pub fn eco_night() -> Message {
+    let mut msg = Message::new();
+    msg.set_on(true);
+    msg.set_mode(Mode::Cold);
+    msg.set_fan(Fan::Level1);
+    msg.set_temperature(Temperature::Centigrade(26));
+    msg.set_sleep(true);
+    msg.set_econo(true);
+    msg.set_light(false);
+    msg
+}
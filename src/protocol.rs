@@ -0,0 +1,77 @@
+#[cfg(all(feature = "encode", feature = "decode"))]
+use crate::DecodeError;
+use crate::{Code, RawFrame, FRAME_LEN};
+#[cfg(feature = "encode")]
+use crate::{EncodeIter, Message};
+
+/// An IR protocol, so a receive loop can try several of them on the same capture.
+///
+/// Protocols work on [`Code`]s, the marks and spaces already classified by length.
+/// Turning them into timings is up to the transmitter.
+pub trait IrProtocol: Sized {
+    /// A short name for logs, e.g. `"gree"`.
+    const NAME: &'static str;
+
+    type Codes: Iterator<Item = Code>;
+    type Error;
+
+    /// The codes to send for `self`.
+    fn to_codes(&self) -> Self::Codes;
+
+    /// Decodes the frame at the start of a capture, failing if it is not one of this
+    /// protocol.
+    fn from_codes(codes: &[Code]) -> Result<Self, Self::Error>;
+}
+
+#[cfg(all(feature = "encode", feature = "decode"))]
+impl IrProtocol for Message {
+    const NAME: &'static str = "gree";
+
+    type Codes = EncodeIter;
+    type Error = DecodeError;
+
+    fn to_codes(&self) -> Self::Codes {
+        self.encode()
+    }
+
+    fn from_codes(codes: &[Code]) -> Result<Self, Self::Error> {
+        Message::decode_slice(codes).map(|(message, _)| message)
+    }
+}
+
+/// A frame the crate can send, so transmitters and schedulers can be written once for
+/// every kind of frame.
+pub trait IrCommand {
+    type Codes: Iterator<Item = Code>;
+
+    fn encode_codes(&self) -> Self::Codes;
+
+    /// The number of codes [`encode_codes`](IrCommand::encode_codes) yields.
+    fn frame_len(&self) -> usize;
+}
+
+#[cfg(feature = "encode")]
+impl IrCommand for Message {
+    type Codes = EncodeIter;
+
+    fn encode_codes(&self) -> Self::Codes {
+        self.encode()
+    }
+
+    fn frame_len(&self) -> usize {
+        FRAME_LEN
+    }
+}
+
+/// Sends the codes as they are, without repairing them.
+impl IrCommand for RawFrame {
+    type Codes = core::array::IntoIter<Code, FRAME_LEN>;
+
+    fn encode_codes(&self) -> Self::Codes {
+        self.0.into_iter()
+    }
+
+    fn frame_len(&self) -> usize {
+        FRAME_LEN
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{Code, Message, FRAME_LEN};
+
+/// Number of durations in [`Message::encode_pulses`]: a mark and a space for every code
+/// but the end marker, which is a lone mark.
+pub const PULSE_LEN: usize = FRAME_LEN * 2 - 1;
+
+impl Code {
+    /// The length of the mark and of the space that follows it, in microseconds.
+    ///
+    /// The space after [`Code::End`] is 0, the frame is over.
+    pub const fn timing(&self) -> (u16, u16) {
+        match self {
+            Code::Start => (9000, 4500),
+            Code::Continue => (620, 19980),
+            Code::End => (620, 0),
+            Code::Short => (620, 540),
+            Code::Long => (620, 1600),
+        }
+    }
+}
+
+impl Message {
+    /// The frame as alternating mark and space durations in microseconds, starting with a
+    /// mark, for transmitters that take raw timings.
+    ///
+    /// It is `const`, see [`gree_frame!`](crate::gree_frame) for fixed frames.
+    pub const fn encode_pulses(&self) -> [u16; PULSE_LEN] {
+        let codes = self.encode_frame();
+        let mut pulses = [0; PULSE_LEN];
+        let mut i = 0;
+        while i < FRAME_LEN {
+            let (mark, space) = codes[i].timing();
+            pulses[i * 2] = mark;
+            if i * 2 + 1 < PULSE_LEN {
+                pulses[i * 2 + 1] = space;
+            }
+            i += 1;
+        }
+        pulses
+    }
+}
+
+/// Declares a `static` holding the [pulses](Message::encode_pulses) of a fixed frame,
+/// computed at compile time.
+///
+/// Fields are named like the getters of [`Message`], and those left out keep the values of
+/// [`Message::new`]. Fields are set in the order given, so `se` has to come after `mode`.
+///
+/// ```
+/// use gree_ir::{gree_frame, Fan, Mode, Temperature, PULSE_LEN};
+///
+/// gree_frame!(static POWER_OFF {});
+/// gree_frame!(pub static COOL_24 {
+///     on: true,
+///     mode: Mode::Cold,
+///     fan: Fan::Auto,
+///     temperature: Temperature::Centigrade(24),
+/// });
+///
+/// let pulses: &[u16; PULSE_LEN] = &COOL_24;
+/// assert_eq!(pulses[..2], [9000, 4500]);
+/// ```
+///
+/// Fields without a `const` setter, such as the timer, are rejected.
+#[macro_export]
+macro_rules! gree_frame {
+    ($vis:vis static $name:ident { $($field:ident: $value:expr),* $(,)? }) => {
+        $vis static $name: [u16; $crate::PULSE_LEN] = {
+            #[allow(unused_mut)]
+            let mut msg = $crate::Message::new();
+            $($crate::gree_frame!(@set msg, $field, $value);)*
+            msg.encode_pulses()
+        };
+    };
+    (@set $msg:ident, on, $value:expr) => { $msg.set_on($value) };
+    (@set $msg:ident, mode, $value:expr) => { $msg.set_mode($value) };
+    (@set $msg:ident, fan, $value:expr) => { $msg.set_fan($value) };
+    (@set $msg:ident, swing, $value:expr) => { $msg.set_swing($value) };
+    (@set $msg:ident, sleep, $value:expr) => { $msg.set_sleep($value) };
+    (@set $msg:ident, temperature, $value:expr) => { $msg.set_temperature($value) };
+    (@set $msg:ident, turbo, $value:expr) => { $msg.set_turbo($value) };
+    (@set $msg:ident, light, $value:expr) => { $msg.set_light($value) };
+    (@set $msg:ident, health, $value:expr) => { $msg.set_health($value) };
+    (@set $msg:ident, dry, $value:expr) => { $msg.set_dry($value) };
+    (@set $msg:ident, ventilate, $value:expr) => { $msg.set_ventilateo($value) };
+    (@set $msg:ident, v_swing, $value:expr) => { $msg.set_v_swing($value) };
+    (@set $msg:ident, h_swing, $value:expr) => { $msg.set_h_swing($value) };
+    (@set $msg:ident, temperature_display, $value:expr) => {
+        $msg.set_temperature_display($value)
+    };
+    (@set $msg:ident, i_feel, $value:expr) => { $msg.set_i_feel($value) };
+    (@set $msg:ident, wifi, $value:expr) => { $msg.set_wifi($value) };
+    (@set $msg:ident, unit_id, $value:expr) => { $msg.set_unit_id($value) };
+    (@set $msg:ident, econo, $value:expr) => { $msg.set_econo($value) };
+    (@set $msg:ident, se, $value:expr) => { $msg.set_se($value) };
+}
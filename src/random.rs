@@ -0,0 +1,87 @@
+use rand::{Rng, RngExt};
+
+use crate::{
+    validate::Fields, Message, Mode, Model, SwingMode, Temperature, TemperatureDisplay,
+    TimerSetting, UnitId,
+};
+
+impl Message {
+    /// A random state [`Message::validate`] accepts for [`Model::DEFAULT`], e.g. for
+    /// stress-testing a unit.
+    ///
+    /// The mode is picked uniformly first, then every field uniformly among the values
+    /// valid in that mode.
+    pub fn random(rng: &mut (impl Rng + ?Sized)) -> Self {
+        let model = Model::DEFAULT;
+        let mode = pick(
+            rng,
+            &[Mode::Auto, Mode::Cold, Mode::Dry, Mode::Wind, Mode::Hot],
+        );
+        let fan = pick(rng, mode.allowed_fan_speeds());
+
+        let celsius = model.min_celsius..=model.max_celsius;
+        let fahrenheit = model.min_fahrenheit..=model.max_fahrenheit;
+        let index = rng.random_range(0..celsius.len() + fahrenheit.len());
+        let temperature = match index.checked_sub(celsius.len()) {
+            None => Temperature::Centigrade(celsius.start() + index as u8),
+            Some(index) => Temperature::Fahrenheit(fahrenheit.start() + index as u8),
+        };
+
+        // 0–10 hours in half hours, then whole hours up to 24.
+        let steps = rng.random_range(0..=20 + (TimerSetting::MAX_HALF_HOURS - 20) / 2);
+        let half_hours = if steps > 20 {
+            20 + (steps - 20) * 2
+        } else {
+            steps
+        };
+        // Turbo is off in dry and fan mode and never together with sleep.
+        let (turbo, sleep) = if matches!(mode, Mode::Dry | Mode::Wind) {
+            pick(rng, &[(false, false), (false, true)])
+        } else {
+            pick(rng, &[(false, false), (true, false), (false, true)])
+        };
+        // SE and econo are cooling-only.
+        let (se, econo) = if mode == Mode::Cold {
+            (rng.random(), rng.random())
+        } else {
+            (false, false)
+        };
+        let fields = Fields {
+            mode,
+            on: rng.random(),
+            fan,
+            swing: rng.random(),
+            temperature,
+            timer: TimerSetting::rounded(rng.random(), half_hours),
+            turbo,
+            sleep,
+            light: rng.random(),
+            health: rng.random(),
+            dry: rng.random(),
+            ventilate: rng.random(),
+            v_swing: SwingMode::decode(rng.random()),
+            h_swing: SwingMode::decode(rng.random()),
+            temperature_display: TemperatureDisplay::decode(rng.random()),
+            i_feel: rng.random(),
+            wifi: rng.random(),
+            unit_id: pick(rng, &UNIT_IDS),
+            se,
+            econo,
+        };
+        Message::from_fields(model, fields)
+    }
+}
+
+const UNIT_IDS: [Option<UnitId>; 17] = {
+    let mut ids = [None; 17];
+    let mut id = 0;
+    while id < 16 {
+        ids[id as usize + 1] = Some(UnitId(id));
+        id += 1;
+    }
+    ids
+};
+
+fn pick<T: Copy>(rng: &mut (impl Rng + ?Sized), values: &[T]) -> T {
+    values[rng.random_range(0..values.len())]
+}
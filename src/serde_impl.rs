@@ -0,0 +1,169 @@
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    Fan, Message, Mode, Model, SwingMode, Temperature, TemperatureDisplay, TimerSetting, UnitId,
+};
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename = "TimerSetting")]
+struct TimerRepr {
+    enabled: bool,
+    half_hours: u8,
+}
+
+impl Serialize for TimerSetting {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TimerRepr {
+            enabled: self.enabled,
+            half_hours: self.half_hours,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Accepts every timer a frame can carry, so decoded captures round-trip. Whether the
+/// remote can set it is left to [`Message::validate`].
+impl<'de> Deserialize<'de> for TimerSetting {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = TimerRepr::deserialize(deserializer)?;
+        TimerSetting::from_frame_half_hours(repr.enabled, repr.half_hours).map_err(D::Error::custom)
+    }
+}
+
+// The fields of a `Message` by name, as returned by its getters.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename = "Message")]
+struct MessageRepr {
+    mode: Mode,
+    on: bool,
+    fan: Fan,
+    swing: bool,
+    sleep: bool,
+    temperature: Temperature,
+    timer: TimerSetting,
+    turbo: bool,
+    light: bool,
+    health: bool,
+    dry: bool,
+    ventilate: bool,
+    v_swing: SwingMode,
+    h_swing: SwingMode,
+    temperature_display: TemperatureDisplay,
+    i_feel: bool,
+    wifi: bool,
+    unit_id: Option<u8>,
+    econo: bool,
+    se: bool,
+    #[serde(default)]
+    model: Model,
+}
+
+/// Human-readable formats like JSON get the fields by name. Bits outside of the known
+/// fields are not kept there, and messages with an invalid mode, temperature or timer fail
+/// to serialize.
+///
+/// Binary formats get the 8 bytes of [`Message::raw`] instead, which keep every bit but
+/// not the model.
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return self.remote_state.serialize(serializer);
+        }
+        MessageRepr {
+            mode: self.mode().map_err(S::Error::custom)?,
+            on: self.is_on(),
+            fan: self.fan(),
+            swing: self.swing(),
+            sleep: self.sleep(),
+            temperature: self.temperature().map_err(S::Error::custom)?,
+            timer: self.timer().map_err(S::Error::custom)?,
+            turbo: self.turbo(),
+            light: self.light(),
+            health: self.health(),
+            dry: self.dry(),
+            ventilate: self.ventilate(),
+            v_swing: self.v_swing(),
+            h_swing: self.h_swing(),
+            temperature_display: self.temperature_display(),
+            i_feel: self.i_feel(),
+            wifi: self.wifi(),
+            unit_id: self.unit_id().map(UnitId::get),
+            econo: self.econo(),
+            se: self.se(),
+            model: self.model,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Fails for temperatures out of the model's range, or for binary formats, for a wrong
+/// checksum.
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            let msg = Message {
+                remote_state: <[u8; 8]>::deserialize(deserializer)?,
+                model: Model::DEFAULT,
+            };
+            msg.check_checksum().map_err(D::Error::custom)?;
+            return Ok(msg);
+        }
+        let repr = MessageRepr::deserialize(deserializer)?;
+        if !repr.model.supports_temperature(repr.temperature) {
+            return Err(D::Error::custom("temperature out of the model's range"));
+        }
+        let unit_id = repr
+            .unit_id
+            .map(UnitId::try_from)
+            .transpose()
+            .map_err(D::Error::custom)?;
+        Ok(Message::builder()
+            .model(repr.model)
+            .mode(repr.mode)
+            .on(repr.on)
+            .fan(repr.fan)
+            .swing(repr.swing)
+            .sleep(repr.sleep)
+            .temperature(repr.temperature)
+            .timer(repr.timer)
+            .turbo(repr.turbo)
+            .light(repr.light)
+            .health(repr.health)
+            .dry(repr.dry)
+            .ventilate(repr.ventilate)
+            .v_swing(repr.v_swing)
+            .h_swing(repr.h_swing)
+            .temperature_display(repr.temperature_display)
+            .i_feel(repr.i_feel)
+            .wifi(repr.wifi)
+            .unit_id(unit_id)
+            .econo(repr.econo)
+            .se(repr.se)
+            .build())
+    }
+}
+
+/// Describes the human-readable form, binary formats don't use schemas.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Message {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        MessageRepr::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        MessageRepr::json_schema(generator)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for TimerSetting {
+    fn schema_name() -> alloc::borrow::Cow<'static, str> {
+        TimerRepr::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        TimerRepr::json_schema(generator)
+    }
+}
@@ -0,0 +1,89 @@
+//! proptest strategies for valid states and broken frames.
+
+use alloc::vec::Vec;
+
+use proptest::{
+    option,
+    prelude::{any, prop_oneof, Strategy},
+    sample::select,
+};
+
+use crate::{
+    corrupt::Corruption, validate::Fields, Fan, Message, Mode, Model, SwingMode, Temperature,
+    TemperatureDisplay, TimerSetting, UnitId,
+};
+
+pub fn mode() -> impl Strategy<Value = Mode> {
+    select(&[Mode::Auto, Mode::Cold, Mode::Dry, Mode::Wind, Mode::Hot][..])
+}
+
+pub fn fan() -> impl Strategy<Value = Fan> {
+    select(&[Fan::Auto, Fan::Level1, Fan::Level2, Fan::Level3][..])
+}
+
+/// Temperatures `model` supports, in either unit.
+pub fn temperature(model: Model) -> impl Strategy<Value = Temperature> {
+    prop_oneof![
+        (model.min_celsius.max(16)..=model.max_celsius.min(31)).prop_map(Temperature::Centigrade),
+        (model.min_fahrenheit.max(61)..=model.max_fahrenheit.min(88))
+            .prop_map(Temperature::Fahrenheit),
+    ]
+}
+
+/// Timers the remote can set.
+pub fn timer() -> impl Strategy<Value = TimerSetting> {
+    (any::<bool>(), 0..=TimerSetting::MAX_HALF_HOURS)
+        .prop_map(|(enabled, half_hours)| TimerSetting::rounded(enabled, half_hours))
+}
+
+/// States [`Message::validate`] accepts for `model`, any fields made valid with
+/// [`Message::sanitize`].
+pub fn message(model: Model) -> impl Strategy<Value = Message> {
+    (
+        mode(),
+        fan(),
+        temperature(model),
+        timer(),
+        any::<[bool; 12]>(),
+        0..16u8,
+        0..16u8,
+        0..4u8,
+        option::of((0..=UnitId::MAX).prop_map(UnitId)),
+    )
+        .prop_map(
+            move |(mode, fan, temperature, timer, flags, v_swing, h_swing, display, unit_id)| {
+                let [on, swing, sleep, turbo, light, health, dry, ventilate, i_feel, wifi, se, econo] =
+                    flags;
+                Message::from_fields(
+                    model,
+                    Fields {
+                        mode,
+                        on,
+                        fan,
+                        swing,
+                        temperature,
+                        timer,
+                        turbo,
+                        sleep,
+                        light,
+                        health,
+                        dry,
+                        ventilate,
+                        v_swing: SwingMode::decode(v_swing),
+                        h_swing: SwingMode::decode(h_swing),
+                        temperature_display: TemperatureDisplay::decode(display),
+                        i_feel,
+                        wifi,
+                        unit_id,
+                        se,
+                        econo,
+                    },
+                )
+            },
+        )
+}
+
+/// Any of [`Corruption::all`].
+pub fn corruption() -> impl Strategy<Value = Corruption> {
+    select(Corruption::all().collect::<Vec<_>>())
+}
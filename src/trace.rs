@@ -0,0 +1,24 @@
+use crate::{DecodeError, FieldInfo};
+
+/// Receives progress from [`Message::decode_traced`](crate::Message::decode_traced), e.g. to
+/// emit `defmt` or `log` traces of where a capture stops making sense.
+///
+/// Both methods do nothing by default.
+pub trait DecodeTrace {
+    /// Called for each part of the frame in order: `start`, `block1`, `magic`, `continue`,
+    /// `block2`, `end` and `checksum`. `raw` holds the bits of the blocks and the checksum,
+    /// LSB first, and is 0 for the markers and the magic.
+    ///
+    /// Decoding stops after the first part with an error.
+    fn part(&mut self, name: &'static str, raw: u32, result: &Result<(), DecodeError>) {
+        let _ = (name, raw, result);
+    }
+
+    /// Called for every field once the whole frame decoded.
+    fn field(&mut self, info: &FieldInfo) {
+        let _ = info;
+    }
+}
+
+/// Traces nothing.
+impl DecodeTrace for () {}
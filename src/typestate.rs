@@ -0,0 +1,207 @@
+//! A [`MessageBuilder`] that only offers the settings the selected mode can use.
+//!
+//! ```
+//! use gree_ir::{typestate::TypedBuilder, Fan, Temperature};
+//!
+//! let msg = TypedBuilder::new()
+//!     .cool()
+//!     .fan(Fan::Level2)
+//!     .temperature(Temperature::Centigrade(24))
+//!     .build();
+//! ```
+//!
+//! Setting the fan speed in dry mode, or the temperature in auto mode, doesn't compile:
+//!
+//! ```compile_fail
+//! use gree_ir::{typestate::TypedBuilder, Fan};
+//!
+//! TypedBuilder::new().dehumidify().fan(Fan::Level3).build();
+//! ```
+
+use core::marker::PhantomData;
+
+use crate::{
+    Fan, Message, MessageBuilder, Mode, Model, SwingMode, Temperature, TemperatureDisplay,
+    TimerSetting, UnitId,
+};
+
+/// No mode selected yet.
+pub struct Unset;
+/// [`Mode::Auto`], the temperature is fixed at 25 ℃.
+pub struct Auto;
+/// [`Mode::Cold`].
+pub struct Cool;
+/// [`Mode::Dry`], the fan speed is chosen by the unit.
+pub struct Dry;
+/// [`Mode::Wind`].
+pub struct FanOnly;
+/// [`Mode::Hot`].
+pub struct Heat;
+
+/// Modes in which the fan speed can be chosen.
+pub trait FanControl {}
+impl FanControl for Auto {}
+impl FanControl for Cool {}
+impl FanControl for FanOnly {}
+impl FanControl for Heat {}
+
+/// Modes in which the temperature can be chosen.
+pub trait TemperatureControl {}
+impl TemperatureControl for Cool {}
+impl TemperatureControl for Dry {}
+impl TemperatureControl for Heat {}
+
+pub struct TypedBuilder<M> {
+    inner: MessageBuilder,
+    mode: PhantomData<M>,
+}
+
+impl TypedBuilder<Unset> {
+    pub fn new() -> Self {
+        Self {
+            inner: MessageBuilder::new(),
+            mode: PhantomData,
+        }
+    }
+
+    pub fn auto(self) -> TypedBuilder<Auto> {
+        self.with_mode(Mode::Auto)
+            .with(|b| b.temperature(Temperature::Centigrade(25)))
+    }
+
+    pub fn cool(self) -> TypedBuilder<Cool> {
+        self.with_mode(Mode::Cold)
+    }
+
+    pub fn dehumidify(self) -> TypedBuilder<Dry> {
+        self.with_mode(Mode::Dry).with(|b| b.fan(Fan::Level1))
+    }
+
+    pub fn fan_only(self) -> TypedBuilder<FanOnly> {
+        self.with_mode(Mode::Wind)
+    }
+
+    pub fn heat(self) -> TypedBuilder<Heat> {
+        self.with_mode(Mode::Hot)
+    }
+
+    fn with_mode<M>(self, mode: Mode) -> TypedBuilder<M> {
+        TypedBuilder {
+            inner: self.inner.mode(mode),
+            mode: PhantomData,
+        }
+    }
+}
+
+impl Default for TypedBuilder<Unset> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: FanControl> TypedBuilder<M> {
+    pub fn fan(self, fan: Fan) -> Self {
+        self.with(|b| b.fan(fan))
+    }
+}
+
+impl<M: TemperatureControl> TypedBuilder<M> {
+    pub fn temperature(self, temp: Temperature) -> Self {
+        self.with(|b| b.temperature(temp))
+    }
+}
+
+impl<M> TypedBuilder<M> {
+    fn with(self, f: impl FnOnce(MessageBuilder) -> MessageBuilder) -> Self {
+        Self {
+            inner: f(self.inner),
+            mode: PhantomData,
+        }
+    }
+
+    pub fn model(self, model: Model) -> Self {
+        self.with(|b| b.model(model))
+    }
+
+    pub fn on(self, on: bool) -> Self {
+        self.with(|b| b.on(on))
+    }
+
+    pub fn swing(self, swing: bool) -> Self {
+        self.with(|b| b.swing(swing))
+    }
+
+    pub fn sleep(self, sleep: bool) -> Self {
+        self.with(|b| b.sleep(sleep))
+    }
+
+    pub fn timer(self, setting: TimerSetting) -> Self {
+        self.with(|b| b.timer(setting))
+    }
+
+    pub fn turbo(self, turbo: bool) -> Self {
+        self.with(|b| b.turbo(turbo))
+    }
+
+    pub fn light(self, light: bool) -> Self {
+        self.with(|b| b.light(light))
+    }
+
+    pub fn health(self, health: bool) -> Self {
+        self.with(|b| b.health(health))
+    }
+
+    pub fn dry(self, dry: bool) -> Self {
+        self.with(|b| b.dry(dry))
+    }
+
+    pub fn ventilate(self, ventilate: bool) -> Self {
+        self.with(|b| b.ventilate(ventilate))
+    }
+
+    pub fn v_swing(self, mode: SwingMode) -> Self {
+        self.with(|b| b.v_swing(mode))
+    }
+
+    pub fn h_swing(self, mode: SwingMode) -> Self {
+        self.with(|b| b.h_swing(mode))
+    }
+
+    pub fn temperature_display(self, temp_display: TemperatureDisplay) -> Self {
+        self.with(|b| b.temperature_display(temp_display))
+    }
+
+    pub fn i_feel(self, i_feel: bool) -> Self {
+        self.with(|b| b.i_feel(i_feel))
+    }
+
+    pub fn wifi(self, wifi: bool) -> Self {
+        self.with(|b| b.wifi(wifi))
+    }
+
+    pub fn unit_id(self, unit_id: Option<UnitId>) -> Self {
+        self.with(|b| b.unit_id(unit_id))
+    }
+
+    pub fn econo(self, econo: bool) -> Self {
+        self.with(|b| b.econo(econo))
+    }
+
+    pub fn se(self, se: bool) -> Self {
+        self.with(|b| b.se(se))
+    }
+}
+
+macro_rules! impl_build {
+    ($($mode:ty),*) => {
+        $(
+            impl TypedBuilder<$mode> {
+                pub fn build(&self) -> Message {
+                    self.inner.build()
+                }
+            }
+        )*
+    };
+}
+
+impl_build!(Auto, Cool, Dry, FanOnly, Heat);
@@ -0,0 +1,351 @@
+#[cfg(feature = "encode")]
+use crate::EncodeIter;
+use crate::{Fan, Message, Mode, SwingMode, Temperature, TimerSetting};
+#[cfg(any(feature = "arbitrary", feature = "proptest", feature = "rand"))]
+use crate::{Model, TemperatureDisplay, UnitId};
+
+/// A known AC constraint that a [`Message`] breaks.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[non_exhaustive]
+pub enum Violation {
+    InvalidMode,
+    InvalidTemperature,
+    InvalidTimer,
+    SeOutsideCool,
+    EconoOutsideCool,
+    TurboInDryOrFan,
+    TurboWithSleep,
+    FanNotAllowed,
+    UnsupportedSwing,
+}
+
+impl Violation {
+    const ALL: [Violation; 9] = [
+        Violation::InvalidMode,
+        Violation::InvalidTemperature,
+        Violation::InvalidTimer,
+        Violation::SeOutsideCool,
+        Violation::EconoOutsideCool,
+        Violation::TurboInDryOrFan,
+        Violation::TurboWithSleep,
+        Violation::FanNotAllowed,
+        Violation::UnsupportedSwing,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Violation::InvalidMode => "mode is not one of auto, cool, dry, fan or heat",
+            Violation::InvalidTemperature => "temperature is out of the model's range",
+            Violation::InvalidTimer => "timer is not a setting the remote can make",
+            Violation::SeOutsideCool => "SE is only available in cool mode",
+            Violation::EconoOutsideCool => "econo is only honoured in cool mode",
+            Violation::TurboInDryOrFan => "turbo is not available in dry or fan mode",
+            Violation::TurboWithSleep => "turbo and sleep can't be on at the same time",
+            Violation::FanNotAllowed => "fan speed is locked in this mode",
+            Violation::UnsupportedSwing => "the model has no such louver setting",
+        }
+    }
+}
+
+/// The set of violations found by [`Message::validate`], iterated in declaration order.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct Violations(u32);
+
+impl Violations {
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, violation: Violation) -> bool {
+        self.0 >> violation as u32 & 1 != 0
+    }
+
+    fn insert(&mut self, violation: Violation) {
+        self.0 |= 1 << violation as u32;
+    }
+}
+
+impl Iterator for Violations {
+    type Item = Violation;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let violation = *Violation::ALL.get(self.0.trailing_zeros() as usize)?;
+        self.0 &= self.0 - 1;
+        Some(violation)
+    }
+}
+
+impl Message {
+    /// Checks the whole state against the known AC constraints.
+    pub fn validate(&self) -> Violations {
+        let mut violations = Violations::default();
+        let mode = self.mode();
+        if mode.is_err() {
+            violations.insert(Violation::InvalidMode);
+        }
+        if self.temperature().is_err() {
+            violations.insert(Violation::InvalidTemperature);
+        }
+        let timer_ok = self
+            .timer()
+            .and_then(|t| TimerSetting::from_half_hours(t.enabled, t.half_hours));
+        if timer_ok.is_err() {
+            violations.insert(Violation::InvalidTimer);
+        }
+        if self.se() && !matches!(mode, Ok(Mode::Cold)) {
+            violations.insert(Violation::SeOutsideCool);
+        }
+        if self.econo() && !matches!(mode, Ok(Mode::Cold)) {
+            violations.insert(Violation::EconoOutsideCool);
+        }
+        if self.turbo() && matches!(mode, Ok(Mode::Dry | Mode::Wind)) {
+            violations.insert(Violation::TurboInDryOrFan);
+        }
+        if self.turbo() && self.sleep() {
+            violations.insert(Violation::TurboWithSleep);
+        }
+        if let Ok(mode) = mode {
+            if !mode.allowed_fan_speeds().contains(&self.fan()) {
+                violations.insert(Violation::FanNotAllowed);
+            }
+        }
+        if !self.model.supports_v_swing(self.v_swing())
+            || !self.model.supports_h_swing(self.h_swing())
+        {
+            violations.insert(Violation::UnsupportedSwing);
+        }
+        violations
+    }
+
+    /// Like [`Message::try_encode`], but checks everything [`Message::validate`] does, so
+    /// only frames the AC accepts as they are get sent.
+    #[cfg(feature = "encode")]
+    pub fn encode_validated(&self) -> Result<EncodeIter, Violations> {
+        let violations = self.validate();
+        if violations.is_empty() {
+            Ok(self.encode())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Fixes every violation [`Message::validate`] would report and returns what was changed.
+    ///
+    /// Temperatures are clamped into the model's range, timers rounded to the closest
+    /// setting the remote can make, unknown modes become auto, and stray features are
+    /// cleared.
+    pub fn sanitize(&mut self) -> Violations {
+        let violations = self.validate();
+        for violation in violations {
+            match violation {
+                Violation::InvalidMode => self.set_mode(Mode::Auto),
+                Violation::InvalidTemperature => self.clamp_temperature(),
+                Violation::InvalidTimer => {
+                    let timer = match self.timer() {
+                        Ok(timer) => TimerSetting::rounded(timer.enabled, timer.half_hours),
+                        Err(_) => TimerSetting::OFF,
+                    };
+                    // Both candidates are in range, so this can't fail.
+                    let _ = self.set_timer(&timer);
+                }
+                Violation::SeOutsideCool => self.set_se(false),
+                Violation::EconoOutsideCool => self.set_econo(false),
+                Violation::TurboInDryOrFan => self.set_turbo(false),
+                // Sleep is kept, being the quieter of the two.
+                Violation::TurboWithSleep => self.set_turbo(false),
+                Violation::UnsupportedSwing => {
+                    if !self.model.supports_v_swing(self.v_swing()) {
+                        self.set_v_swing(SwingMode::Off);
+                    }
+                    if !self.model.supports_h_swing(self.h_swing()) {
+                        self.set_h_swing(SwingMode::Off);
+                    }
+                }
+                Violation::FanNotAllowed => {
+                    let fan = self.effective_fan();
+                    self.set_fan(fan);
+                }
+            }
+        }
+        violations
+    }
+
+    /// The fan speed the unit actually runs at, which in dry mode is low whatever the
+    /// frame says.
+    pub fn effective_fan(&self) -> Fan {
+        let fan = self.fan();
+        match self.mode() {
+            Ok(mode) if !mode.allowed_fan_speeds().contains(&fan) => {
+                mode.allowed_fan_speeds().first().copied().unwrap_or(fan)
+            }
+            _ => fan,
+        }
+    }
+
+    /// Sets the setpoint to 25 ℃ (77 ℉ if the unit shows fahrenheit) in auto mode, where
+    /// the AC ignores it, the way the remote does. Returns whether anything changed.
+    ///
+    /// Frames in other modes are left alone.
+    pub fn normalize_auto_temperature(&mut self) -> bool {
+        if !matches!(self.mode(), Ok(Mode::Auto)) {
+            return false;
+        }
+        let temp = match self.temperature() {
+            Ok(Temperature::Fahrenheit(_)) => Temperature::Fahrenheit(77),
+            _ => Temperature::Centigrade(25),
+        };
+        if self.temperature() == Ok(temp) {
+            return false;
+        }
+        self.set_temperature(temp);
+        true
+    }
+
+    fn clamp_temperature(&mut self) {
+        let fahrenheit = self.remote_state[3] >> 3 & 1 != 0;
+        let temp = Temperature::decode(
+            self.remote_state[1],
+            self.remote_state[3] >> 2 & 1 != 0,
+            fahrenheit,
+        );
+        let (min, max) = if fahrenheit {
+            (
+                self.model.min_fahrenheit.max(61),
+                self.model.max_fahrenheit.min(88),
+            )
+        } else {
+            (
+                self.model.min_celsius.max(16),
+                self.model.max_celsius.min(31),
+            )
+        };
+        let degree = temp.degrees().max(min).min(max);
+        let temp = if fahrenheit {
+            Temperature::Fahrenheit(degree)
+        } else {
+            Temperature::Centigrade(degree)
+        };
+        self.set_temperature(temp);
+    }
+}
+
+/// Every setting of a [`Message`], as drawn by the random generators before
+/// [`Message::from_fields`] makes them valid.
+#[cfg(any(feature = "arbitrary", feature = "proptest", feature = "rand"))]
+pub(crate) struct Fields {
+    pub mode: Mode,
+    pub on: bool,
+    pub fan: Fan,
+    pub swing: bool,
+    pub temperature: Temperature,
+    pub timer: TimerSetting,
+    pub turbo: bool,
+    pub sleep: bool,
+    pub light: bool,
+    pub health: bool,
+    pub dry: bool,
+    pub ventilate: bool,
+    pub v_swing: SwingMode,
+    pub h_swing: SwingMode,
+    pub temperature_display: TemperatureDisplay,
+    pub i_feel: bool,
+    pub wifi: bool,
+    pub unit_id: Option<UnitId>,
+    pub se: bool,
+    pub econo: bool,
+}
+
+#[cfg(any(feature = "arbitrary", feature = "proptest", feature = "rand"))]
+impl Message {
+    /// A message for `model` with every field set, then [sanitized](Message::sanitize).
+    pub(crate) fn from_fields(model: Model, fields: Fields) -> Self {
+        let mut msg = Message::with_model(model);
+        msg.set_mode(fields.mode);
+        msg.set_on(fields.on);
+        msg.set_fan(fields.fan);
+        msg.set_swing(fields.swing);
+        msg.set_temperature(fields.temperature);
+        let _ = msg.set_timer(&fields.timer);
+        msg.set_turbo(fields.turbo);
+        msg.set_sleep(fields.sleep);
+        msg.set_light(fields.light);
+        msg.set_health(fields.health);
+        msg.set_dry(fields.dry);
+        msg.set_ventilateo(fields.ventilate);
+        msg.set_v_swing(fields.v_swing);
+        msg.set_h_swing(fields.h_swing);
+        msg.set_temperature_display(fields.temperature_display);
+        msg.set_i_feel(fields.i_feel);
+        msg.set_wifi(fields.wifi);
+        msg.set_unit_id(fields.unit_id);
+        msg.set_se(fields.se);
+        msg.set_econo(fields.econo);
+        msg.sanitize();
+        msg
+    }
+}
+
+// The assertions need `Debug`.
+#[cfg(all(test, feature = "fmt"))]
+mod tests {
+    use crate::{Message, Model, Temperature, Violation};
+
+    #[test]
+    fn sanitize_clamps_fahrenheit_to_the_closer_end() {
+        let mut msg = Message::with_model(Model::EXTENDED);
+        msg.set_temperature(Temperature::Fahrenheit(87));
+        msg.set_model(Model::DEFAULT);
+        assert!(msg.validate().contains(Violation::InvalidTemperature));
+        msg.sanitize();
+        assert_eq!(msg.temperature(), Ok(Temperature::Fahrenheit(86)));
+        assert!(msg.validate().is_empty());
+
+        // 89 ℉ is on the wire, but beyond what the protocol is meant to carry.
+        let mut msg = Message::with_model(Model::EXTENDED);
+        msg.remote_state[1] |= 0x0F;
+        msg.remote_state[3] |= 0b1100;
+        msg.update_checksum();
+        assert_eq!(
+            msg.temperature(),
+            Err(crate::DecodeError::InvalidTemperature)
+        );
+        msg.sanitize();
+        assert_eq!(msg.temperature(), Ok(Temperature::Fahrenheit(88)));
+    }
+
+    #[test]
+    fn sanitize_clamps_celsius() {
+        let mut msg = Message::with_model(Model::EXTENDED);
+        msg.set_temperature(Temperature::Centigrade(31));
+        msg.set_model(Model::DEFAULT);
+        msg.sanitize();
+        assert_eq!(msg.temperature(), Ok(Temperature::Centigrade(30)));
+    }
+
+    #[test]
+    fn fahrenheit_round_trips_through_the_table() {
+        for degree in 61..=88 {
+            let temp = Temperature::Fahrenheit(degree);
+            let (value, extra, fahrenheit) = temp.encode().unwrap();
+            assert_eq!(Temperature::decode(value, extra, fahrenheit), temp);
+        }
+    }
+
+    #[test]
+    fn validate_reports_every_violation() {
+        let mut msg = Message::new();
+        msg.set_mode(crate::Mode::Dry);
+        msg.set_turbo(true);
+        msg.set_sleep(true);
+        msg.set_econo(true);
+        let violations = msg.validate();
+        assert!(violations.contains(Violation::TurboInDryOrFan));
+        assert!(violations.contains(Violation::TurboWithSleep));
+        assert!(violations.contains(Violation::EconoOutsideCool));
+        msg.sanitize();
+        assert!(msg.validate().is_empty());
+        assert!(msg.sleep() && !msg.turbo() && !msg.econo());
+    }
+}